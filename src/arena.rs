@@ -0,0 +1,222 @@
+use {
+    crate::*,
+    core::{cell::RefCell, slice, str},
+    ministr::NonEmptyStr,
+};
+
+/// Default size in bytes of a [`FilePathArena`] chunk.
+pub const DEFAULT_ARENA_CHUNK_SIZE: usize = 64 * 1024;
+
+/// An arena allocator for [`FilePath`]'s.
+///
+/// Validates the pushed paths and copies their canonical string representations to large heap-allocated chunks,
+/// avoiding a heap allocation per file path.
+/// Returned [`FilePath`]'s borrow the arena and use the canonical string representation (i.e. same as [`FilePathBuf`]'s).
+///
+/// Chunks are only freed when the arena is dropped; [`reset`](Self::reset) allows reusing them.
+pub struct FilePathArena {
+    inner: RefCell<FilePathArenaInner>,
+    chunk_size: usize,
+}
+
+struct FilePathArenaInner {
+    /// Current chunk. Never grows past its capacity, so the pushed paths are never moved.
+    chunk: String,
+    /// Full chunks, in push order.
+    full_chunks: Vec<String>,
+    /// Used to validate and canonicalize the pushed paths.
+    builder: FilePathBuilder,
+    /// Number of pushed file paths.
+    len: usize,
+}
+
+impl FilePathArena {
+    /// Creates an empty [`FilePathArena`] with [`DEFAULT_ARENA_CHUNK_SIZE`] byte chunks.
+    ///
+    /// Does not allocate until the first file path is pushed.
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_ARENA_CHUNK_SIZE)
+    }
+
+    /// Creates an empty [`FilePathArena`] with (at least) `chunk_size` byte chunks.
+    ///
+    /// File paths longer than `chunk_size` are allocated in their own chunks.
+    ///
+    /// Does not allocate until the first file path is pushed.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            inner: RefCell::new(FilePathArenaInner {
+                chunk: String::new(),
+                full_chunks: Vec::new(),
+                builder: FilePathBuilder::new(),
+                len: 0,
+            }),
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// Returns the number of file paths pushed to the arena since its creation or last [`reset`](Self::reset).
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total capacity in bytes of all chunks allocated by the arena.
+    pub fn capacity(&self) -> usize {
+        let inner = self.inner.borrow();
+        inner.chunk.capacity()
+            + inner
+                .full_chunks
+                .iter()
+                .map(String::capacity)
+                .sum::<usize>()
+    }
+
+    /// Tries to validate the [`path`](RawPath) and copy it to the arena.
+    ///
+    /// Returns the (canonical) [`FilePath`] borrowed from the arena,
+    /// or an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`FilePath`].
+    pub fn push<P: AsRef<RawPath>>(&self, path: P) -> Result<&FilePath, FilePathError> {
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+
+        inner.builder.clear();
+        inner.builder.push(path)?;
+
+        if inner.builder.is_empty() {
+            return Err(FilePathError::EmptyPath);
+        }
+
+        let path = inner.push_built_path(self.chunk_size);
+        // Contains a copy of a valid canonical path.
+        // The chunk's heap buffer is never reallocated or freed while the arena is borrowed.
+        Ok(unsafe { FilePath::from_str(NonEmptyStr::new_unchecked(&*path)) })
+    }
+
+    /// Tries to validate the `path` string and copy it to the arena.
+    ///
+    /// See [`push`](Self::push).
+    pub fn push_str(&self, path: &str) -> Result<&FilePath, FilePathError> {
+        self.push(path)
+    }
+
+    /// Tries to validate all the `paths` and copy them to the arena.
+    ///
+    /// Returns the (canonical) [`FilePath`]'s borrowed from the arena, in order,
+    /// or the index of the first invalid path and the [`error`](FilePathError).
+    /// Previously pushed `paths` remain in the arena in the latter case.
+    pub fn push_all<I, P>(&self, paths: I) -> Result<Vec<&FilePath>, (usize, FilePathError)>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<RawPath>,
+    {
+        paths
+            .into_iter()
+            .enumerate()
+            .map(|(idx, path)| self.push(path).map_err(|err| (idx, err)))
+            .collect()
+    }
+
+    /// Clears the arena, invalidating all previously pushed [`FilePath`]'s.
+    ///
+    /// Keeps the largest allocated chunk for reuse, freeing the rest.
+    pub fn reset(&mut self) {
+        let inner = self.inner.get_mut();
+
+        if let Some(largest_chunk) = inner.full_chunks.drain(..).max_by_key(String::capacity)
+            && largest_chunk.capacity() > inner.chunk.capacity()
+        {
+            inner.chunk = largest_chunk;
+        }
+
+        inner.chunk.clear();
+        inner.len = 0;
+    }
+}
+
+impl FilePathArenaInner {
+    /// Copies the path built by the `builder` to the arena and returns the pointer to the copy.
+    fn push_built_path(&mut self, chunk_size: usize) -> *const str {
+        let path = self.builder.as_mut_string();
+
+        if self.chunk.capacity() - self.chunk.len() < path.len() {
+            let chunk = core::mem::replace(
+                &mut self.chunk,
+                String::with_capacity(chunk_size.max(path.len())),
+            );
+            if chunk.capacity() > 0 {
+                self.full_chunks.push(chunk);
+            }
+        }
+
+        let start = self.chunk.len();
+        // Does not reallocate.
+        self.chunk.push_str(path);
+        self.len += 1;
+
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.chunk.as_ptr().add(start),
+                path.len(),
+            ))
+        }
+    }
+}
+
+impl Default for FilePathArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena() {
+        let mut arena = FilePathArena::with_chunk_size(16);
+        assert!(arena.is_empty());
+        assert_eq!(arena.capacity(), 0);
+
+        let a = arena.push("foo//bar/./baz.txt").unwrap();
+        let b = arena.push_str("bill.cfg").unwrap();
+        let c = arena.push_str("some/very/long/file/path.txt").unwrap();
+
+        assert_eq!(
+            arena.push_str("foo/../bar").err().unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
+        );
+        assert_eq!(arena.push_str("").err().unwrap(), FilePathError::EmptyPath);
+
+        assert_eq!(a.as_str(), "foo/bar/baz.txt");
+        assert_eq!(b.as_str(), "bill.cfg");
+        assert_eq!(c.as_str(), "some/very/long/file/path.txt");
+        assert_eq!(arena.len(), 3);
+        assert!(arena.capacity() >= 16 + "some/very/long/file/path.txt".len());
+
+        let paths = arena.push_all(["a", "b/c", "d"]).unwrap();
+        assert_eq!(
+            paths.iter().map(|path| path.as_str()).collect::<Vec<_>>(),
+            ["a", "b/c", "d"]
+        );
+        assert_eq!(arena.len(), 6);
+
+        assert_eq!(
+            arena.push_all(["e", "f/", "/g"]).err().unwrap(),
+            (2, FilePathError::RootDirectory)
+        );
+        assert_eq!(arena.len(), 8);
+
+        let capacity = arena.capacity();
+        arena.reset();
+        assert!(arena.is_empty());
+        assert!(arena.capacity() <= capacity);
+        assert!(arena.capacity() >= 16);
+
+        assert_eq!(arena.push_str("foo").unwrap().as_str(), "foo");
+    }
+}
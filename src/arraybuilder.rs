@@ -0,0 +1,394 @@
+use {
+    crate::*,
+    core::{
+        fmt::{Debug, Formatter},
+        ops::{Deref, DerefMut},
+        str,
+    },
+    ministr::NonEmptyStr,
+};
+
+/// Fixed-capacity builder for a [`FilePath`], which stores the built path in an inline `N` byte buffer.
+///
+/// Same as [`FilePathBuilder`], but never allocates (except for the [`error`](FilePathError) paths),
+/// and returns a [`FilePathError::CapacityExceeded`] error if the built path would become longer than `N` bytes.
+///
+/// The built path uses the canonical string representation (i.e. same as [`FilePathBuf`]'s)
+/// and is [`borrowed`](Self::as_file_path) from the builder.
+#[derive(Clone)]
+pub struct ArrayFilePathBuilder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ArrayFilePathBuilder<N> {
+    /// Creates an empty [`ArrayFilePathBuilder`].
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the capacity in bytes of the builder, i.e. `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the length in bytes of the built path. May be zero for an empty builder.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of components of the built path. May be zero for an empty builder.
+    pub fn depth(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            self.bytes()
+                .iter()
+                .filter(|b| **b == SEPARATOR_BYTE)
+                .count()
+                + 1
+        }
+    }
+
+    /// Attempts to append the `path` to the built path.
+    ///
+    /// Returns an [`error`](FilePathError) if the `path` contains an invalid component,
+    /// or if the built path would become too long or exceed the builder's capacity.
+    /// The built path is unchanged in this case.
+    pub fn push<P: AsRef<RawPath>>(&mut self, path: P) -> Result<(), FilePathError> {
+        let path = FilePath::new(path.as_ref())?;
+
+        let path_len = path.components().fold(self.len, |path_len, component| {
+            // Count the separator.
+            path_len + if path_len == 0 { 0 } else { 1 } + component.len()
+        });
+        self.check_len(path_len)?;
+
+        for component in path.components() {
+            self.append_component(component);
+        }
+
+        Ok(())
+    }
+
+    /// Attempts to append a single (already validated) path `component` to the built path.
+    ///
+    /// Does not need to validate the `component` and is thus cheaper than [`push`](Self::push).
+    ///
+    /// Returns an [`error`](FilePathError) if the built path would become too long or exceed the builder's capacity;
+    /// the built path is unchanged in this case.
+    pub fn push_component(&mut self, component: &FileName) -> Result<(), FilePathError> {
+        self.push_component_impl(component)
+    }
+
+    /// Attempts to append all path `components` to the built path.
+    ///
+    /// Returns an [`error`](FilePathError) if the built path would become too long or exceed the builder's capacity.
+    /// The built path is unchanged in this case.
+    pub fn try_extend<'a, I: IntoIterator<Item = &'a FileName>>(
+        &mut self,
+        components: I,
+    ) -> Result<(), FilePathError> {
+        let checkpoint = self.checkpoint();
+
+        for component in components {
+            if let Err(err) = self.push_component(component) {
+                self.rollback(checkpoint);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Attempts to pop the last (leaf) path component of the built path.
+    ///
+    /// Returns `true` if the built path was not empty and the last path component was popped.
+    pub fn pop(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        self.len = self
+            .bytes()
+            .iter()
+            .rposition(|b| *b == SEPARATOR_BYTE)
+            .unwrap_or(0);
+
+        true
+    }
+
+    /// Shortens the built path to its first `depth` components.
+    ///
+    /// Does nothing if the built path has `depth` or fewer components.
+    pub fn truncate(&mut self, depth: usize) {
+        if let Some(depth) = depth.checked_sub(1) {
+            if let Some((idx, _)) = self
+                .bytes()
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b == SEPARATOR_BYTE)
+                .nth(depth)
+            {
+                self.len = idx;
+            }
+        } else {
+            self.clear();
+        }
+    }
+
+    /// See [`FilePathBuilder::checkpoint`].
+    pub fn checkpoint(&self) -> FilePathBuilderCheckpoint {
+        FilePathBuilderCheckpoint(self.len)
+    }
+
+    /// See [`FilePathBuilder::rollback`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the built path was shortened since the `checkpoint` was made
+    /// such that it is shorter than at the `checkpoint`, or the `checkpoint` no longer falls on a path component boundary.
+    pub fn rollback(&mut self, checkpoint: FilePathBuilderCheckpoint) {
+        assert!(
+            self.try_rollback(checkpoint),
+            "invalid `ArrayFilePathBuilder` checkpoint"
+        );
+    }
+
+    fn try_rollback(&mut self, checkpoint: FilePathBuilderCheckpoint) -> bool {
+        let len = checkpoint.0;
+        if len == self.len || len == 0 || (len < self.len && self.buf[len] == SEPARATOR_BYTE) {
+            self.len = len;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// See [`FilePathBuilder::scope`].
+    pub fn scope(&mut self) -> ArrayFilePathBuilderScope<'_, N> {
+        ArrayFilePathBuilderScope {
+            checkpoint: self.checkpoint(),
+            builder: self,
+        }
+    }
+
+    /// Clears the built path.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// If the builder is non-empty, returns the built (canonical) [`FilePath`].
+    pub fn as_file_path(&self) -> Option<&FilePath> {
+        // Only contains valid canonical paths.
+        NonEmptyStr::new(self.as_str()).map(|path| unsafe { FilePath::from_str(path) })
+    }
+
+    /// Returns the built path string. May be empty for an empty builder.
+    pub fn as_str(&self) -> &str {
+        // Only contains valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.bytes()) }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn check_len(&self, path_len: usize) -> Result<(), FilePathError> {
+        if path_len > MAX_PATH_LEN {
+            Err(FilePathError::PathTooLong(path_len))
+        } else if path_len > N {
+            Err(FilePathError::CapacityExceeded(path_len))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn push_component_impl(&mut self, component: &FileName) -> Result<(), FilePathError> {
+        // Count the separator.
+        let path_len = if self.is_empty() {
+            component.len()
+        } else {
+            self.len + 1 + component.len()
+        };
+        self.check_len(path_len)?;
+        self.append_component(component);
+        Ok(())
+    }
+
+    /// The caller guarantees the `component` fits.
+    fn append_component(&mut self, component: &FileName) {
+        if !self.is_empty() {
+            self.buf[self.len] = SEPARATOR_BYTE;
+            self.len += 1;
+        }
+        self.buf[self.len..self.len + component.len()].copy_from_slice(component.as_bytes());
+        self.len += component.len();
+    }
+}
+
+impl<const N: usize> Default for ArrayFilePathBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Debug for ArrayFilePathBuilder<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ArrayFilePathBuilder")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+/// A scope guard which dereferences to an [`ArrayFilePathBuilder`] and [`rolls back`](ArrayFilePathBuilder::rollback)
+/// all path components appended via it when dropped.
+///
+/// See [`FilePathBuilderScope`] and [`ArrayFilePathBuilder::scope`].
+pub struct ArrayFilePathBuilderScope<'a, const N: usize> {
+    builder: &'a mut ArrayFilePathBuilder<N>,
+    checkpoint: FilePathBuilderCheckpoint,
+}
+
+impl<'a, const N: usize> Deref for ArrayFilePathBuilderScope<'a, N> {
+    type Target = ArrayFilePathBuilder<N>;
+
+    fn deref(&self) -> &Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, const N: usize> DerefMut for ArrayFilePathBuilderScope<'a, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, const N: usize> Drop for ArrayFilePathBuilderScope<'a, N> {
+    fn drop(&mut self) {
+        self.builder.try_rollback(self.checkpoint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let mut builder = ArrayFilePathBuilder::<16>::new();
+        assert!(builder.is_empty());
+        assert_eq!(builder.capacity(), 16);
+        assert!(builder.as_file_path().is_none());
+
+        assert_eq!(
+            builder.push("/foo").err().unwrap(),
+            FilePathError::RootDirectory
+        );
+        assert_eq!(
+            builder.push("foo/../").err().unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
+        );
+
+        builder.push("foo//./bar").unwrap();
+        assert_eq!(builder.as_str(), "foo/bar");
+        assert_eq!(builder.depth(), 2);
+
+        builder
+            .push_component(FileName::new("baz.txt").unwrap())
+            .unwrap();
+        assert_eq!(
+            builder.as_file_path().unwrap(),
+            FilePath::new("foo/bar/baz.txt").unwrap()
+        );
+        assert_eq!(builder.as_file_path().unwrap().as_str(), "foo/bar/baz.txt");
+
+        // "foo/bar/baz.txt/a" is 17 bytes.
+        assert_eq!(
+            builder.push("a").err().unwrap(),
+            FilePathError::CapacityExceeded(17)
+        );
+        assert_eq!(
+            builder
+                .push_component(FileName::new("a").unwrap())
+                .err()
+                .unwrap(),
+            FilePathError::CapacityExceeded(17)
+        );
+        assert_eq!(
+            builder
+                .try_extend([FileName::new("a").unwrap()])
+                .err()
+                .unwrap(),
+            FilePathError::CapacityExceeded(17)
+        );
+        assert_eq!(builder.as_str(), "foo/bar/baz.txt");
+
+        assert!(builder.pop());
+        assert_eq!(builder.as_str(), "foo/bar");
+        {
+            let mut scope = builder.scope();
+            scope.push("a/b").unwrap();
+            assert_eq!(scope.as_str(), "foo/bar/a/b");
+            scope.truncate(3);
+            assert_eq!(scope.as_str(), "foo/bar/a");
+        }
+        assert_eq!(builder.as_str(), "foo/bar");
+
+        // Partially fits.
+        assert_eq!(
+            builder.push("a/bcdefgh").err().unwrap(),
+            FilePathError::CapacityExceeded(17)
+        );
+        assert_eq!(builder.as_str(), "foo/bar");
+
+        assert!(builder.pop());
+        assert!(builder.pop());
+        assert!(!builder.pop());
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn path_too_long() {
+        let mut builder = ArrayFilePathBuilder::<{ MAX_PATH_LEN + 16 }>::new();
+        let long_component = "a".repeat(MAX_COMPONENT_LEN);
+        for _ in 0..MAX_PATH_LEN / (MAX_COMPONENT_LEN + 1) {
+            builder.push(&long_component).unwrap();
+        }
+        let remaining = MAX_PATH_LEN - builder.len() - 1;
+        builder.push("b".repeat(remaining)).unwrap();
+        assert_eq!(builder.len(), MAX_PATH_LEN);
+
+        // Must report `PathTooLong` even if the path fits in the buffer.
+        assert_eq!(
+            builder.push("c").err().unwrap(),
+            FilePathError::PathTooLong(MAX_PATH_LEN + 2)
+        );
+        assert_eq!(builder.len(), MAX_PATH_LEN);
+    }
+
+    #[test]
+    fn scope_truncated() {
+        let mut builder = ArrayFilePathBuilder::<16>::new();
+        builder.push("a/b").unwrap();
+        {
+            let mut scope = builder.scope();
+            scope.push("c").unwrap();
+            scope.truncate(1);
+        }
+        assert_eq!(builder.as_file_path().unwrap().as_str(), "a");
+        {
+            let mut scope = builder.scope();
+            scope.clear();
+            scope.push("bcd").unwrap();
+        }
+        assert_eq!(builder.as_file_path().unwrap().as_str(), "bcd");
+    }
+}
@@ -0,0 +1,322 @@
+use {
+    crate::*,
+    alloc::{rc::Rc, sync::Arc},
+    core::iter::DoubleEndedIterator,
+    ministr::NonEmptyStr,
+};
+
+/// Shared (query) API of the [`FilePath`]-like types, allowing generic code to abstract over them
+/// (e.g. take an `impl AsFilePath` instead of a `&FilePath` or a `FilePathBuf`) without unnecessary conversions.
+///
+/// Implemented by [`FilePath`], [`FilePathBuf`], [`SmallFilePathBuf`], [`FileName`], [`FileNameBuf`],
+/// boxed / shared [`FilePath`]'s and references to all of them.
+///
+/// Only [`as_file_path`](Self::as_file_path) and [`components`](Self::components) must be implemented,
+/// the rest of the methods are provided.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{AsFilePath, FilePath, FilePathBuf};
+///
+/// fn is_texture(path: impl AsFilePath) -> bool {
+///     path.extension().is_some_and(|extension| extension.as_str() == "png")
+/// }
+///
+/// assert!(is_texture(FilePath::new("textures//props/barrel.png").unwrap()));
+/// assert!(is_texture(&FilePathBuf::new("textures/props/barrel.png").unwrap()));
+/// assert!(!is_texture(FilePathBuf::new("meshes/props/barrel.fbx").unwrap()));
+/// ```
+#[allow(clippy::len_without_is_empty)]
+pub trait AsFilePath {
+    /// [`Iterator`] over the [`file name`](FileName) components of the path, root to leaf.
+    type Components<'a>: DoubleEndedIterator<Item = &'a FileName>
+    where
+        Self: 'a;
+
+    /// Returns the path as a (maybe non-canonical) [`FilePath`].
+    fn as_file_path(&self) -> &FilePath;
+
+    /// Returns an [`iterator`](Self::Components) over the [`file name`](FileName) components of the path, root to leaf.
+    ///
+    /// NOTE: file name, with extension or not, is a single component.
+    ///
+    /// NOTE: can be reversed via `rev()` to iterate leaf to root.
+    fn components(&self) -> Self::Components<'_>;
+
+    /// Returns the length in bytes of the path string. Always > 0.
+    ///
+    /// NOTE: the length of a non-canonical [`FilePath`] may differ from the length of its canonical [`FilePathBuf`].
+    fn len(&self) -> usize {
+        self.as_file_path().len()
+    }
+
+    fn as_ne_str(&self) -> &NonEmptyStr {
+        self.as_file_path().as_ne_str()
+    }
+
+    fn as_str(&self) -> &str {
+        self.as_file_path().as_str()
+    }
+
+    #[cfg(feature = "std")]
+    fn as_path(&self) -> &std::path::Path {
+        self.as_file_path().as_path()
+    }
+
+    /// Returns the file name portion of the path (i.e. the last/leaf component).
+    fn file_name(&self) -> &FileName {
+        unsafe {
+            self.components()
+                .next_back()
+                .unwrap_unchecked_dbg_msg("empty `FilePath`'s are invalid")
+        }
+    }
+
+    /// Returns the file stem portion of the path (i.e. the non-extension part of the last/leaf component).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension`].
+    fn file_stem(&self) -> Option<&NonEmptyStr> {
+        self.file_name().file_stem()
+    }
+
+    /// Returns the extension portion of the path (i.e. the extension part of the last/leaf component).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension`].
+    fn extension(&self) -> Option<&NonEmptyStr> {
+        self.file_name().extension()
+    }
+
+    /// Returns `true` if the path starts with all the components of the `base` path (compared componentwise).
+    ///
+    /// NOTE: a path starts with itself.
+    fn starts_with<B: AsFilePath + ?Sized>(&self, base: &B) -> bool {
+        let mut components = self.components();
+        base.components()
+            .all(|base| components.next().is_some_and(|component| component == base))
+    }
+
+    /// Returns `true` if the path uses the canonical string representation (i.e. same as [`FilePathBuf`]'s):
+    /// only normal path components separated by single forward slashes.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{AsFilePath, FilePath};
+    ///
+    /// assert!(FilePath::new("foo/bar/baz.txt").unwrap().is_canonical());
+    /// assert!(!FilePath::new("foo//bar/./baz.txt/").unwrap().is_canonical());
+    /// ```
+    fn is_canonical(&self) -> bool {
+        Iterator::eq(
+            self.as_str().split(SEPARATOR_CHAR),
+            self.components().map(FileName::as_str),
+        )
+    }
+
+    /// Appends the compact binary encoding of the path to the `buf`:
+    /// the little-endian `u16` length in bytes of the canonical string representation of the path,
+    /// followed by the canonical string itself.
+    ///
+    /// Use [`FilePath::decode`] to decode it (zero-copy).
+    /// Also see [`encode_components`](Self::encode_components) for a layout which is faster to iterate.
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_path(self.components(), buf)
+    }
+
+    /// Appends the compact binary per-component encoding of the path to the `buf`:
+    /// the little-endian `u16` length in bytes of the canonical string representation of the path,
+    /// followed by all of its components, each preceded by its `u8` length in bytes
+    /// (i.e. `len + 1` bytes in total).
+    ///
+    /// Use [`EncodedComponents::decode`] to decode it (zero-copy).
+    fn encode_components(&self, buf: &mut Vec<u8>) {
+        encode_path_components(self.components(), buf)
+    }
+
+    /// Returns the (relative) URL path of the path:
+    /// each component percent-encoded per RFC 3986 `pchar` (as UTF-8), joined with `/`.
+    ///
+    /// Use [`FilePathBuf::from_url_path`] to convert it back.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{AsFilePath, FilePath};
+    ///
+    /// let path = FilePath::new("textures//barrel #1/café.png").unwrap();
+    /// assert_eq!(path.to_url_path(), "textures/barrel%20%231/caf%C3%A9.png");
+    /// ```
+    fn to_url_path(&self) -> String {
+        url_path(self.components(), self.len())
+    }
+}
+
+impl AsFilePath for FilePath {
+    type Components<'a> = FilePathIter<'a>;
+
+    fn as_file_path(&self) -> &FilePath {
+        self
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        FilePath::components(self)
+    }
+}
+
+impl AsFilePath for FilePathBuf {
+    type Components<'a> = FilePathBufIter<'a>;
+
+    fn as_file_path(&self) -> &FilePath {
+        FilePathBuf::as_file_path(self)
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        // Unlike `FilePath`, we may use the simpler iterator because of the `FilePathBuf`'s canonical string representation.
+        FilePathBufIter::new(self.as_file_path())
+    }
+}
+
+impl AsFilePath for SmallFilePathBuf {
+    type Components<'a> = FilePathBufIter<'a>;
+
+    fn as_file_path(&self) -> &FilePath {
+        SmallFilePathBuf::as_file_path(self)
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        // Same canonical string representation as `FilePathBuf`.
+        FilePathBufIter::new(self.as_file_path())
+    }
+}
+
+impl AsFilePath for FileName {
+    type Components<'a> = FilePathBufIter<'a>;
+
+    fn as_file_path(&self) -> &FilePath {
+        FileName::as_file_path(self)
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        // A file name is a canonical single component file path.
+        FilePathBufIter::new(self.as_file_path())
+    }
+}
+
+impl AsFilePath for FileNameBuf {
+    type Components<'a> = FilePathBufIter<'a>;
+
+    fn as_file_path(&self) -> &FilePath {
+        self.as_file_name().as_file_path()
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        AsFilePath::components(self.as_file_name())
+    }
+}
+
+/// Boxed / shared [`FilePath`]'s always use the canonical string representation,
+/// so may use the simpler [`FilePathBufIter`].
+macro_rules! impl_as_file_path_for_canonical {
+    ($($ty:ty),*) => {
+        $(
+            impl AsFilePath for $ty {
+                type Components<'a> = FilePathBufIter<'a>;
+
+                fn as_file_path(&self) -> &FilePath {
+                    self
+                }
+
+                fn components(&self) -> Self::Components<'_> {
+                    FilePathBufIter::new(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_as_file_path_for_canonical!(Box<FilePath>, Arc<FilePath>, Rc<FilePath>);
+
+impl<T: AsFilePath + ?Sized> AsFilePath for &T {
+    type Components<'a>
+        = T::Components<'a>
+    where
+        Self: 'a;
+
+    fn as_file_path(&self) -> &FilePath {
+        T::as_file_path(self)
+    }
+
+    fn components(&self) -> Self::Components<'_> {
+        T::components(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query<P: AsFilePath>(path: P) -> (usize, String, Option<String>, Vec<String>) {
+        (
+            path.len(),
+            path.file_name().to_string(),
+            path.extension().map(ToString::to_string),
+            path.components().rev().map(ToString::to_string).collect(),
+        )
+    }
+
+    #[test]
+    fn as_file_path() {
+        let path = FilePath::new("foo/bar/baz.txt").unwrap();
+        let expected = (
+            15,
+            String::from("baz.txt"),
+            Some(String::from("txt")),
+            vec![
+                String::from("baz.txt"),
+                String::from("bar"),
+                String::from("foo"),
+            ],
+        );
+
+        assert_eq!(query(path), expected);
+        assert_eq!(query(path.to_owned()), expected);
+        let path_buf = path.to_owned();
+        let path_buf_ref = &path_buf;
+        assert_eq!(query(path_buf_ref), expected);
+        assert_eq!(query(SmallFilePathBuf::from(path)), expected);
+        assert_eq!(query(Box::<FilePath>::from(path.to_owned())), expected);
+        assert_eq!(query(Arc::<FilePath>::from(path.to_owned())), expected);
+        let rc = Rc::<FilePath>::from(path.to_owned());
+        let rc_ref_ref = &&rc;
+        assert_eq!(query(rc_ref_ref), expected);
+
+        // Non-canonical.
+        let path = FilePath::new("foo//bar/./baz.txt").unwrap();
+        assert_eq!(query(path).1, "baz.txt");
+        assert_eq!(query(path).3, expected.3);
+
+        let name = FileName::new("baz.txt").unwrap();
+        assert_eq!(
+            query(name),
+            (7, expected.1.clone(), expected.2, vec![expected.1])
+        );
+
+        assert!(path.starts_with(FilePath::new("foo/bar").unwrap()));
+        assert!(AsFilePath::starts_with(
+            &path.to_owned(),
+            FileName::new("foo").unwrap()
+        ));
+        assert!(!AsFilePath::starts_with(
+            &path,
+            FileName::new("bar").unwrap()
+        ));
+
+        // Non-canonical paths are encoded in the canonical string representation.
+        let path_buf = path.to_owned();
+        assert!(!path.is_canonical());
+        assert!(path_buf.is_canonical());
+        assert_eq!(path.to_url_path(), path_buf.to_url_path());
+        let (mut buf, mut buf_) = (Vec::new(), Vec::new());
+        path.encode(&mut buf);
+        SmallFilePathBuf::from(path).encode(&mut buf_);
+        assert_eq!(buf, buf_);
+    }
+}
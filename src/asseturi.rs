@@ -0,0 +1,417 @@
+use {
+    crate::*,
+    core::{
+        error::Error,
+        fmt::{Display, Formatter},
+        str::FromStr,
+    },
+};
+
+/// Separator between the scheme and the path of an [`AssetUri`].
+pub const SCHEME_SEPARATOR_CHAR: char = ':';
+
+/// Separator between the path and the fragment of an [`AssetUri`].
+pub const FRAGMENT_SEPARATOR_CHAR: char = '#';
+
+/// A borrowed scheme-qualified asset URI, `<scheme>:<path>[#<fragment>]`
+/// (e.g. `engine:shaders/common.hlsl`, `game:models/hero.fbx#mesh/body`).
+///
+/// See [`AssetUri`] for the owned version.
+///
+/// - The scheme is an identifier: an ASCII letter followed by ASCII letters, digits, `+`, `-` or `.`
+///   (as in RFC 3986).
+/// - The path is a [`FilePath`] which does not contain a `#`
+///   (as `:` is an invalid path character, the scheme never collides with a [`FilePath`]).
+/// - The optional fragment is a [`FilePath`] addressing a sub-resource of the asset.
+///
+/// Compared / hashed by the scheme, then by the path and fragment components.
+/// [`Formatted`](Display) using the canonical string representations of the path and fragment.
+///
+/// E.g.
+/// ```
+/// use minifilepath::AssetUriRef;
+///
+/// let uri = AssetUriRef::parse("game:models//hero.fbx#mesh/body").unwrap();
+/// assert_eq!(uri.scheme(), "game");
+/// assert_eq!(uri.path(), "models/hero.fbx");
+/// assert_eq!(uri.fragment().unwrap(), "mesh/body");
+/// assert_eq!(uri.to_string(), "game:models/hero.fbx#mesh/body");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct AssetUriRef<'a> {
+    scheme: &'a str,
+    path: &'a FilePath,
+    fragment: Option<&'a FilePath>,
+}
+
+impl<'a> AssetUriRef<'a> {
+    /// Tries to create an [`AssetUriRef`] from its `scheme`, `path` and optional `fragment`.
+    ///
+    /// Returns an [`error`](AssetUriError) if the `scheme` is not a valid identifier,
+    /// or if the `path` contains a `#`.
+    pub fn new(
+        scheme: &'a str,
+        path: &'a FilePath,
+        fragment: Option<&'a FilePath>,
+    ) -> Result<Self, AssetUriError> {
+        validate_scheme(scheme)?;
+        validate_uri_path(path)?;
+
+        Ok(Self {
+            scheme,
+            path,
+            fragment,
+        })
+    }
+
+    /// Tries to parse an `<scheme>:<path>[#<fragment>]` `uri`, without copying it.
+    ///
+    /// The path ends at the first `#`; the (maybe non-canonical) path and fragment are validated as [`FilePath`]'s.
+    pub fn parse(uri: &'a str) -> Result<Self, AssetUriError> {
+        let (scheme, rest) = uri
+            .split_once(SCHEME_SEPARATOR_CHAR)
+            .ok_or(AssetUriError::MissingScheme)?;
+        validate_scheme(scheme)?;
+
+        let (path, fragment) = match rest.split_once(FRAGMENT_SEPARATOR_CHAR) {
+            Some((path, fragment)) => (path, Some(fragment)),
+            None => (rest, None),
+        };
+
+        Ok(Self {
+            scheme,
+            path: FilePath::new(path).map_err(AssetUriError::InvalidPath)?,
+            fragment: fragment
+                .map(|fragment| FilePath::new(fragment).map_err(AssetUriError::InvalidFragment))
+                .transpose()?,
+        })
+    }
+
+    pub fn scheme(&self) -> &'a str {
+        self.scheme
+    }
+
+    pub fn path(&self) -> &'a FilePath {
+        self.path
+    }
+
+    pub fn fragment(&self) -> Option<&'a FilePath> {
+        self.fragment
+    }
+
+    /// Returns the [`AssetUriRef`] of the asset itself, i.e. without the fragment.
+    pub fn without_fragment(&self) -> Self {
+        Self {
+            fragment: None,
+            ..*self
+        }
+    }
+
+    /// Returns an owned [`AssetUri`] copy of the [`AssetUriRef`] (with a canonical path and fragment).
+    pub fn to_asset_uri(&self) -> AssetUri {
+        AssetUri {
+            scheme: self.scheme.to_owned(),
+            path: self.path.to_owned(),
+            fragment: self.fragment.map(FilePath::to_owned),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AssetUriRef<'a> {
+    type Error = AssetUriError;
+
+    fn try_from(uri: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(uri)
+    }
+}
+
+impl Display for AssetUriRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.scheme,
+            SCHEME_SEPARATOR_CHAR,
+            Canonical(self.path)
+        )?;
+        if let Some(fragment) = self.fragment {
+            write!(f, "{}{}", FRAGMENT_SEPARATOR_CHAR, Canonical(fragment))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq<AssetUri> for AssetUriRef<'_> {
+    fn eq(&self, other: &AssetUri) -> bool {
+        *self == other.as_uri_ref()
+    }
+}
+
+/// An owned scheme-qualified asset URI, `<scheme>:<path>[#<fragment>]`
+/// (e.g. `engine:shaders/common.hlsl`, `game:models/hero.fbx#mesh/body`).
+///
+/// See [`AssetUriRef`] for the borrowed version and the format.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{AssetUri, FilePathBuf};
+///
+/// let uri: AssetUri = "engine:shaders/./common.hlsl".parse().unwrap();
+/// assert_eq!(uri.path(), "shaders/common.hlsl");
+/// assert!(uri.fragment().is_none());
+/// assert_eq!(
+///     uri,
+///     AssetUri::new("engine", FilePathBuf::new("shaders/common.hlsl").unwrap(), None).unwrap()
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct AssetUri {
+    scheme: String,
+    path: FilePathBuf,
+    fragment: Option<FilePathBuf>,
+}
+
+impl AssetUri {
+    /// Tries to create an [`AssetUri`] from its `scheme`, `path` and optional `fragment`.
+    ///
+    /// Returns an [`error`](AssetUriError) if the `scheme` is not a valid identifier,
+    /// or if the `path` contains a `#`.
+    pub fn new(
+        scheme: &str,
+        path: FilePathBuf,
+        fragment: Option<FilePathBuf>,
+    ) -> Result<Self, AssetUriError> {
+        validate_scheme(scheme)?;
+        validate_uri_path(&path)?;
+
+        Ok(Self {
+            scheme: scheme.to_owned(),
+            path,
+            fragment,
+        })
+    }
+
+    /// Tries to parse an `<scheme>:<path>[#<fragment>]` `uri`.
+    ///
+    /// See [`AssetUriRef::parse`].
+    pub fn parse(uri: &str) -> Result<Self, AssetUriError> {
+        AssetUriRef::parse(uri).map(|uri| uri.to_asset_uri())
+    }
+
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    pub fn path(&self) -> &FilePath {
+        &self.path
+    }
+
+    pub fn fragment(&self) -> Option<&FilePath> {
+        self.fragment.as_deref()
+    }
+
+    pub fn as_uri_ref(&self) -> AssetUriRef<'_> {
+        AssetUriRef {
+            scheme: &self.scheme,
+            path: &self.path,
+            fragment: self.fragment(),
+        }
+    }
+
+    /// Replaces the fragment of the [`AssetUri`], returning the previous one.
+    pub fn set_fragment(&mut self, fragment: Option<FilePathBuf>) -> Option<FilePathBuf> {
+        core::mem::replace(&mut self.fragment, fragment)
+    }
+
+    /// Consumes the [`AssetUri`], returning its scheme, path and fragment.
+    pub fn into_parts(self) -> (String, FilePathBuf, Option<FilePathBuf>) {
+        (self.scheme, self.path, self.fragment)
+    }
+}
+
+impl FromStr for AssetUri {
+    type Err = AssetUriError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        Self::parse(uri)
+    }
+}
+
+impl TryFrom<&str> for AssetUri {
+    type Error = AssetUriError;
+
+    fn try_from(uri: &str) -> Result<Self, Self::Error> {
+        Self::parse(uri)
+    }
+}
+
+impl<'a> From<AssetUriRef<'a>> for AssetUri {
+    fn from(uri: AssetUriRef<'a>) -> Self {
+        uri.to_asset_uri()
+    }
+}
+
+impl Display for AssetUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.as_uri_ref().fmt(f)
+    }
+}
+
+impl PartialEq<AssetUriRef<'_>> for AssetUri {
+    fn eq(&self, other: &AssetUriRef<'_>) -> bool {
+        self.as_uri_ref() == *other
+    }
+}
+
+/// An error returned when parsing / creating [`AssetUri`]'s.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AssetUriError {
+    /// URI is missing the `:` scheme separator.
+    MissingScheme,
+    /// Scheme is not a valid identifier.
+    /// Contains the scheme.
+    InvalidScheme(String),
+    /// Path is not a valid [`FilePath`], or contains a `#`.
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+    /// Fragment is not a valid [`FilePath`].
+    /// Contains the validation error.
+    InvalidFragment(FilePathError),
+}
+
+impl Error for AssetUriError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AssetUriError::InvalidPath(err) | AssetUriError::InvalidFragment(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AssetUriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use AssetUriError::*;
+
+        match self {
+            MissingScheme => "asset URI is missing the scheme".fmt(f),
+            InvalidScheme(scheme) => write!(f, "invalid asset URI scheme \"{scheme}\""),
+            InvalidPath(err) => write!(f, "invalid asset URI path: {err}"),
+            InvalidFragment(err) => write!(f, "invalid asset URI fragment: {err}"),
+        }
+    }
+}
+
+fn validate_scheme(scheme: &str) -> Result<(), AssetUriError> {
+    let mut bytes = scheme.bytes();
+
+    if bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+    {
+        Ok(())
+    } else {
+        Err(AssetUriError::InvalidScheme(scheme.to_owned()))
+    }
+}
+
+/// The path must not contain the fragment separator.
+fn validate_uri_path(path: &FilePath) -> Result<(), AssetUriError> {
+    let mut error_path = ErrorPath::new();
+
+    for component in path.components() {
+        push_error_path(&mut error_path, component.as_str());
+
+        if component.contains(FRAGMENT_SEPARATOR_CHAR) {
+            return Err(AssetUriError::InvalidPath(FilePathError::InvalidCharacter(
+                (error_path, FRAGMENT_SEPARATOR_CHAR),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashSet};
+
+    #[test]
+    fn parse() {
+        let uri = AssetUriRef::parse("game:models/hero.fbx#mesh/body").unwrap();
+        assert_eq!(uri.scheme(), "game");
+        assert_eq!(uri.path(), "models/hero.fbx");
+        assert_eq!(uri.fragment().unwrap(), "mesh/body");
+        assert_eq!(uri.without_fragment().to_string(), "game:models/hero.fbx");
+
+        let uri = AssetUri::parse("my-engine+v2.0:shaders//./common.hlsl#a#b").unwrap();
+        assert_eq!(uri.scheme(), "my-engine+v2.0");
+        assert_eq!(uri.path().as_str(), "shaders/common.hlsl");
+        assert_eq!(uri.fragment().unwrap().as_str(), "a#b");
+        assert_eq!(uri.to_string(), "my-engine+v2.0:shaders/common.hlsl#a#b");
+        assert_eq!(AssetUri::parse(&uri.to_string()).unwrap(), uri);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            AssetUri::parse("shaders/common.hlsl").err().unwrap(),
+            AssetUriError::MissingScheme
+        );
+        assert_eq!(
+            AssetUri::parse(":shaders/common.hlsl").err().unwrap(),
+            AssetUriError::InvalidScheme(String::new())
+        );
+        assert_eq!(
+            AssetUri::parse("1game:shaders/common.hlsl").err().unwrap(),
+            AssetUriError::InvalidScheme("1game".to_owned())
+        );
+        assert_eq!(
+            AssetUri::parse("game:").err().unwrap(),
+            AssetUriError::InvalidPath(FilePathError::EmptyPath)
+        );
+        assert_eq!(
+            AssetUri::parse("game:a:b").err().unwrap(),
+            AssetUriError::InvalidPath(FilePathError::InvalidCharacter((
+                ErrorPath::from("a:b"),
+                ':'
+            )))
+        );
+        assert_eq!(
+            AssetUri::parse("game:a#").err().unwrap(),
+            AssetUriError::InvalidFragment(FilePathError::EmptyPath)
+        );
+        assert_eq!(
+            AssetUri::parse("game:a#../b").err().unwrap(),
+            AssetUriError::InvalidFragment(FilePathError::ParentDirectory(ErrorPath::new()))
+        );
+        assert_eq!(
+            AssetUri::new("game", FilePathBuf::new("levels/a#1/b.lvl").unwrap(), None)
+                .err()
+                .unwrap(),
+            AssetUriError::InvalidPath(FilePathError::InvalidCharacter((
+                ErrorPath::from("levels/a#1"),
+                '#'
+            )))
+        );
+    }
+
+    #[test]
+    fn cmp_hash() {
+        let a = AssetUri::parse("engine:shaders/common.hlsl").unwrap();
+        let b = AssetUri::parse("game:levels/a.lvl").unwrap();
+        let c = AssetUri::parse("game:levels/a.lvl#spawn").unwrap();
+        let d = AssetUri::parse("game:levels/b.lvl").unwrap();
+
+        let mut uris = vec![d.clone(), c.clone(), b.clone(), a.clone()];
+        uris.sort();
+        assert_eq!(uris, [a.clone(), b.clone(), c.clone(), d.clone()]);
+
+        let borrowed = AssetUriRef::parse("game:levels//a.lvl#spawn").unwrap();
+        assert_eq!(borrowed, c);
+        assert_eq!(c, borrowed);
+        assert_eq!(borrowed.to_asset_uri(), c);
+
+        let set = uris.into_iter().collect::<HashSet<_>>();
+        assert!(set.contains(&AssetUri::from(borrowed)));
+        assert_eq!(set.len(), 4);
+    }
+}
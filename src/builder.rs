@@ -1,4 +1,11 @@
-use {crate::*, ministr::NonEmptyString, std::path::Path};
+use {
+    crate::*,
+    core::{
+        iter::FromIterator,
+        ops::{Deref, DerefMut},
+    },
+    ministr::{NonEmptyStr, NonEmptyString},
+};
 
 /// Builder for a [`FilePathBuf`].
 ///
@@ -26,13 +33,54 @@ impl FilePathBuilder {
         self.len() == 0
     }
 
+    /// Returns the number of components of the built [`FilePathBuf`]. May be zero for an empty builder.
+    pub fn depth(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            self.0.bytes().filter(|b| *b == SEPARATOR_BYTE).count() + 1
+        }
+    }
+
     /// Attempts to append the `path` to the built [`FilePathBuf`].
     ///
-    /// Returns an [`error`](FilePathError) if the `path` contains an invalid component.
-    pub fn push<P: AsRef<Path>>(&mut self, path: P) -> Result<(), FilePathError> {
+    /// Returns an [`error`](FilePathError) if the `path` contains an invalid component,
+    /// or if the built [`FilePathBuf`] would become too long.
+    /// The built [`FilePathBuf`] is unchanged in this case.
+    pub fn push<P: AsRef<RawPath>>(&mut self, path: P) -> Result<(), FilePathError> {
         append_file_path_to_string(FilePath::new(path.as_ref())?, &mut self.0)
     }
 
+    /// Attempts to append a single (already validated) path `component` to the built [`FilePathBuf`].
+    ///
+    /// Does not need to validate the `component` and is thus cheaper than [`push`](Self::push).
+    ///
+    /// Returns an [`error`](FilePathError) if the built [`FilePathBuf`] would become too long;
+    /// the built [`FilePathBuf`] is unchanged in this case.
+    pub fn push_component(&mut self, component: &FileName) -> Result<(), FilePathError> {
+        self.push_component_impl(component)
+    }
+
+    /// Attempts to append all path `components` to the built [`FilePathBuf`].
+    ///
+    /// Returns an [`error`](FilePathError) if the built [`FilePathBuf`] would become too long.
+    /// The built [`FilePathBuf`] is unchanged in this case.
+    pub fn try_extend<'a, I: IntoIterator<Item = &'a FileName>>(
+        &mut self,
+        components: I,
+    ) -> Result<(), FilePathError> {
+        let checkpoint = self.checkpoint();
+
+        for component in components {
+            if let Err(err) = self.push_component(component) {
+                self.rollback(checkpoint);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Attempts to pop the last (leaf) path component of the built [`FilePathBuf`].
     ///
     /// Returns `true` if the built [`FilePathBuf`] was not empty and the last path component was popped.
@@ -48,11 +96,88 @@ impl FilePathBuilder {
         res
     }
 
+    /// Shortens the built [`FilePathBuf`] to its first `depth` components.
+    ///
+    /// Does nothing if the built [`FilePathBuf`] has `depth` or fewer components.
+    pub fn truncate(&mut self, depth: usize) {
+        if let Some(depth) = depth.checked_sub(1) {
+            if let Some((idx, _)) = self.0.match_indices(SEPARATOR_CHAR).nth(depth) {
+                self.0.truncate(idx);
+            }
+        } else {
+            self.clear();
+        }
+    }
+
+    /// Returns the [`checkpoint`](FilePathBuilderCheckpoint) which may be later used to [`rollback`](Self::rollback)
+    /// the built [`FilePathBuf`] to its current state.
+    pub fn checkpoint(&self) -> FilePathBuilderCheckpoint {
+        FilePathBuilderCheckpoint(self.len())
+    }
+
+    /// Rolls back the built [`FilePathBuf`] to the state at the time the `checkpoint` was returned by [`checkpoint`](Self::checkpoint),
+    /// i.e. removes all path components appended since.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the built [`FilePathBuf`] was shortened since the `checkpoint` was made
+    /// such that it is shorter than at the `checkpoint`, or the `checkpoint` no longer falls on a path component boundary.
+    /// Does not check whether the built [`FilePathBuf`] still starts with the path at the `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: FilePathBuilderCheckpoint) {
+        assert!(
+            self.try_rollback(checkpoint),
+            "invalid `FilePathBuilder` checkpoint"
+        );
+    }
+
+    /// Same as [`rollback`](Self::rollback), but returns `false` and leaves the built [`FilePathBuf`] unchanged
+    /// instead of panicking.
+    fn try_rollback(&mut self, checkpoint: FilePathBuilderCheckpoint) -> bool {
+        let len = checkpoint.0;
+        if len == self.len() || len == 0 || self.0.as_bytes().get(len) == Some(&SEPARATOR_BYTE) {
+            self.0.truncate(len);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the [`scope guard`](FilePathBuilderScope) which dereferences to this [`FilePathBuilder`]
+    /// and [`rolls back`](Self::rollback) all path components appended via it when dropped.
+    ///
+    /// Useful for e.g. recursive directory walks.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::*;
+    ///
+    /// let mut builder = FilePathBuilder::new();
+    /// builder.push("foo").unwrap();
+    /// {
+    ///     let mut scope = builder.scope();
+    ///     scope.push("bar/baz").unwrap();
+    ///     assert_eq!(scope.depth(), 3);
+    /// }
+    /// assert_eq!(builder.build().unwrap().as_str(), "foo");
+    /// ```
+    pub fn scope(&mut self) -> FilePathBuilderScope<'_> {
+        FilePathBuilderScope {
+            checkpoint: self.checkpoint(),
+            builder: self,
+        }
+    }
+
     /// Clears the built [`FilePathBuf`], without reducing its capacity.
     pub fn clear(&mut self) {
         self.0.clear();
     }
 
+    /// If the builder is non-empty, returns the built (canonical) [`FilePath`].
+    pub fn as_file_path(&self) -> Option<&FilePath> {
+        // Only contains valid canonical paths.
+        NonEmptyStr::new(&self.0).map(|path| unsafe { FilePath::from_str(path) })
+    }
+
     /// Consumes the [`FilePathBuilder`] and, if it is non-empty, returns the built [`FilePathBuf`].
     pub fn build(self) -> Option<FilePathBuf> {
         NonEmptyString::new(self.0).map(FilePathBuf)
@@ -66,6 +191,27 @@ impl FilePathBuilder {
         Self(buf)
     }
 
+    /// The caller guarantees the built path remains valid.
+    pub(crate) fn as_mut_string(&mut self) -> &mut String {
+        &mut self.0
+    }
+
+    fn push_component_impl(&mut self, component: &FileName) -> Result<(), FilePathError> {
+        // Count the separator.
+        let path_len = if self.is_empty() {
+            component.len()
+        } else {
+            self.len() + 1 + component.len()
+        };
+
+        if path_len > MAX_PATH_LEN {
+            Err(FilePathError::PathTooLong(path_len))
+        } else {
+            append_path_component_to_string(component, &mut self.0);
+            Ok(())
+        }
+    }
+
     #[cfg(test)]
     fn as_str(&self) -> &str {
         self.0.as_str()
@@ -78,8 +224,72 @@ impl Default for FilePathBuilder {
     }
 }
 
+impl<'a> Extend<&'a FileName> for FilePathBuilder {
+    /// Appends all path `components` to the built [`FilePathBuf`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the built [`FilePathBuf`] would become too long.
+    /// See [`try_extend`](FilePathBuilder::try_extend) for the non-panicking version.
+    fn extend<I: IntoIterator<Item = &'a FileName>>(&mut self, components: I) {
+        if let Err(err) = self.try_extend(components) {
+            panic!("failed to extend a `FilePathBuilder`: {}", err);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a FileName> for FilePathBuilder {
+    /// Creates a [`FilePathBuilder`] from path `components`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the built [`FilePathBuf`] would become too long.
+    fn from_iter<I: IntoIterator<Item = &'a FileName>>(components: I) -> Self {
+        let mut builder = Self::new();
+        builder.extend(components);
+        builder
+    }
+}
+
+/// A checkpoint of the [`FilePathBuilder`] state, used to [`rollback`](FilePathBuilder::rollback) the built [`FilePathBuf`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FilePathBuilderCheckpoint(pub(crate) usize);
+
+/// A scope guard which dereferences to a [`FilePathBuilder`] and [`rolls back`](FilePathBuilder::rollback) all path components
+/// appended via it when dropped.
+///
+/// Unlike [`FilePathBuilder::rollback`], does not panic if the built [`FilePathBuf`] was shortened via the guard
+/// past the scope's [`checkpoint`](FilePathBuilder::checkpoint); the built [`FilePathBuf`] is left unchanged in this case.
+///
+/// See [`FilePathBuilder::scope`].
+pub struct FilePathBuilderScope<'a> {
+    builder: &'a mut FilePathBuilder,
+    checkpoint: FilePathBuilderCheckpoint,
+}
+
+impl<'a> Deref for FilePathBuilderScope<'a> {
+    type Target = FilePathBuilder;
+
+    fn deref(&self) -> &Self::Target {
+        self.builder
+    }
+}
+
+impl<'a> DerefMut for FilePathBuilderScope<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.builder
+    }
+}
+
+impl<'a> Drop for FilePathBuilderScope<'a> {
+    fn drop(&mut self) {
+        self.builder.try_rollback(self.checkpoint);
+    }
+}
+
 fn append_file_path_to_string(path: &FilePath, string: &mut String) -> Result<(), FilePathError> {
-    let mut path_len = string.len();
+    let initial_len = string.len();
+    let mut path_len = initial_len;
 
     for component in path.components() {
         // Count the separator.
@@ -95,13 +305,15 @@ fn append_file_path_to_string(path: &FilePath, string: &mut String) -> Result<()
     }
 
     if path_len > MAX_PATH_LEN {
+        // All or nothing.
+        string.truncate(initial_len);
         Err(FilePathError::PathTooLong(path_len))
     } else {
         Ok(())
     }
 }
 
-fn append_path_component_to_string(component: FilePathComponent, string: &mut String) {
+fn append_path_component_to_string(component: &FileName, string: &mut String) {
     if !string.is_empty() {
         string.push(SEPARATOR_CHAR);
     }
@@ -110,7 +322,7 @@ fn append_path_component_to_string(component: FilePathComponent, string: &mut St
 
 #[cfg(test)]
 mod tests {
-    use {super::*, std::path::PathBuf};
+    use super::*;
 
     #[test]
     fn builder() {
@@ -129,11 +341,11 @@ mod tests {
         );
         assert_eq!(
             builder.push("foo/../").err().unwrap(),
-            FilePathError::ParentDirectory(PathBuf::from("foo"))
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
         );
         assert_eq!(
             builder.push("./foo").err().unwrap(),
-            FilePathError::CurrentDirectory(PathBuf::new())
+            FilePathError::CurrentDirectory(ErrorPath::new())
         );
 
         builder.push("foo/./").unwrap();
@@ -166,4 +378,160 @@ mod tests {
         assert_eq!(builder.len(), 0);
         assert_eq!(builder.as_str(), "");
     }
+
+    #[test]
+    fn push_all_or_nothing() {
+        let long_component = "a".repeat(MAX_COMPONENT_LEN);
+        let mut builder = FilePathBuilder::new();
+        for _ in 0..MAX_PATH_LEN / (MAX_COMPONENT_LEN + 1) {
+            builder.push(&long_component).unwrap();
+        }
+        let len = builder.len();
+        let depth = builder.depth();
+
+        // Fits partially.
+        let remaining = MAX_PATH_LEN - len - 1;
+        assert!(remaining <= MAX_COMPONENT_LEN);
+        let path = format!("x/{}", "b".repeat(remaining));
+        assert_eq!(
+            builder.push(&path).err().unwrap(),
+            FilePathError::PathTooLong(MAX_PATH_LEN + 2)
+        );
+        assert_eq!(builder.len(), len);
+        assert_eq!(builder.depth(), depth);
+    }
+
+    #[test]
+    fn push_component() {
+        let name = |name| FileName::new(name).unwrap();
+
+        let mut builder = FilePathBuilder::new();
+        builder.push_component(name("foo")).unwrap();
+        builder.push_component(name("bar.txt")).unwrap();
+        assert_eq!(builder.as_str(), "foo/bar.txt");
+        assert_eq!(builder.depth(), 2);
+
+        let path = FilePath::new("a//b/./c").unwrap();
+        let mut builder: FilePathBuilder = path.components().collect();
+        assert_eq!(builder.as_str(), "a/b/c");
+
+        builder.extend(path.components().skip(1));
+        assert_eq!(builder.as_str(), "a/b/c/b/c");
+
+        let long_component = "a".repeat(MAX_COMPONENT_LEN);
+        let long_component = name(&long_component);
+        // Fails on the last component.
+        let num_components = MAX_PATH_LEN / (MAX_COMPONENT_LEN + 1) + 1;
+        let len = builder.len() + num_components * (MAX_COMPONENT_LEN + 1);
+
+        assert_eq!(
+            builder
+                .try_extend(core::iter::repeat_n(long_component, num_components).chain([name("d")]))
+                .err()
+                .unwrap(),
+            FilePathError::PathTooLong(len)
+        );
+        assert_eq!(builder.as_str(), "a/b/c/b/c");
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_too_long() {
+        let long_component = "a".repeat(MAX_COMPONENT_LEN);
+        let long_component = FileName::new(&long_component).unwrap();
+
+        let mut builder = FilePathBuilder::new();
+        builder.extend(core::iter::repeat_n(
+            long_component,
+            MAX_PATH_LEN / MAX_COMPONENT_LEN,
+        ));
+    }
+
+    #[test]
+    fn truncate_and_rollback() {
+        let mut builder = FilePathBuilder::new();
+        builder.push("a/b/c/d").unwrap();
+
+        builder.truncate(5);
+        assert_eq!(builder.as_str(), "a/b/c/d");
+        builder.truncate(4);
+        assert_eq!(builder.as_str(), "a/b/c/d");
+        builder.truncate(2);
+        assert_eq!(builder.as_str(), "a/b");
+        builder.truncate(0);
+        assert!(builder.is_empty());
+        assert_eq!(builder.depth(), 0);
+
+        let empty = builder.checkpoint();
+        builder.push("a/b").unwrap();
+        let ab = builder.checkpoint();
+        builder.push("c/d").unwrap();
+        builder.rollback(ab);
+        assert_eq!(builder.as_str(), "a/b");
+        // Rolling back to the current state is a no-op.
+        builder.rollback(ab);
+        assert_eq!(builder.as_str(), "a/b");
+        builder.rollback(empty);
+        assert!(builder.is_empty());
+
+        fn walk(builder: &mut FilePathBuilder, depth: usize, paths: &mut Vec<String>) {
+            if depth == 0 {
+                return;
+            }
+            for name in ["x", "y"] {
+                let mut scope = builder.scope();
+                scope.push(name).unwrap();
+                paths.push(scope.as_str().to_owned());
+                walk(&mut scope, depth - 1, paths);
+            }
+        }
+
+        let mut paths = Vec::new();
+        builder.push("root").unwrap();
+        walk(&mut builder, 2, &mut paths);
+        assert_eq!(builder.as_str(), "root");
+        assert_eq!(
+            paths,
+            [
+                "root/x", "root/x/x", "root/x/y", "root/y", "root/y/x", "root/y/y"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rollback_invalid() {
+        let mut builder = FilePathBuilder::new();
+        builder.push("aaaa").unwrap();
+        let checkpoint = builder.checkpoint();
+        builder.clear();
+        builder.push("a/bcdef").unwrap();
+        builder.rollback(checkpoint);
+    }
+
+    #[test]
+    fn scope_truncated() {
+        let mut builder = FilePathBuilder::new();
+        builder.push("a/b").unwrap();
+        {
+            let mut scope = builder.scope();
+            scope.push("c").unwrap();
+            scope.truncate(1);
+        }
+        assert_eq!(builder.as_str(), "a");
+        {
+            let mut scope = builder.scope();
+            scope.clear();
+            scope.push("bcd").unwrap();
+        }
+        // Not on a component boundary - left unchanged.
+        assert_eq!(builder.as_str(), "bcd");
+        {
+            let mut scope = builder.scope();
+            scope.clear();
+            scope.push("xyz/w").unwrap();
+        }
+        // On a component boundary, but the path at the checkpoint is not a prefix - not detected.
+        assert_eq!(builder.as_str(), "xyz");
+    }
 }
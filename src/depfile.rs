@@ -0,0 +1,614 @@
+use {
+    crate::*,
+    core::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// Options of [`Depfile::parse_with`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct DepfileParseOptions<'a> {
+    /// Return dependencies outside of the project root (absolute paths not under the [`root`](Self::root),
+    /// or relative paths with parent directory components resolving to outside of it)
+    /// in [`DepfileRule::external_deps`] instead of failing with an error.
+    pub allow_external: bool,
+    /// Absolute (UTF-8) path of the project root directory, if any.
+    ///
+    /// Absolute targets and dependencies under it are made relative to it.
+    /// Both `/` and `\` separated (e.g. `/home/me/project`, `C:\project`) roots and dependencies are supported.
+    pub root: Option<&'a RawPath>,
+}
+
+/// A `<targets>: <dependencies>` rule of a [`Depfile`].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct DepfileRule {
+    /// Targets of the rule; at least one for parsed rules.
+    pub targets: Vec<FilePathBuf>,
+    /// Dependencies of the targets.
+    pub deps: Vec<FilePathBuf>,
+    /// Dependencies of the targets outside of the project root (as written in the depfile),
+    /// if [`allowed`](DepfileParseOptions::allow_external).
+    pub external_deps: Vec<String>,
+}
+
+/// A Makefile / Ninja dependency file (`.d` file), as emitted by compilers (e.g. `gcc -MD`, `dxc -MF`).
+///
+/// Contains one rule per (logical) line, `<targets>: <dependencies>`, where
+/// - paths are separated by whitespace,
+/// - spaces and `#` in paths are escaped with a backslash (`\ `, `\#`), and `$` is escaped as `$$`,
+/// - a backslash at the end of a line continues the rule on the next line,
+/// - `#` starts a comment until the end of the line.
+///
+/// Paths are relative to the project root; parent directory components (`..`) in targets and dependencies are resolved.
+/// See [`DepfileParseOptions`] for absolute paths and dependencies outside of the project root;
+/// targets outside of the project root are always an error.
+///
+/// [`Formatting`](Display) the [`Depfile`] writes it back with correctly escaped canonical paths.
+///
+/// E.g.
+/// ```
+/// use minifilepath::Depfile;
+///
+/// let depfile = Depfile::parse("shaders/lit.dxil: shaders/lit.hlsl \\\n  shaders/common\\ lib.hlsl\n").unwrap();
+/// assert_eq!(depfile.rules[0].targets[0].as_str(), "shaders/lit.dxil");
+/// assert_eq!(depfile.rules[0].deps[1].as_str(), "shaders/common lib.hlsl");
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Depfile {
+    pub rules: Vec<DepfileRule>,
+}
+
+impl Depfile {
+    /// Tries to parse the depfile `text`, rejecting dependencies outside of the project root.
+    ///
+    /// Returns an [`error`](DepfileError) with the line of the first invalid rule, if any.
+    pub fn parse(text: &str) -> Result<Self, DepfileError> {
+        Self::parse_with(text, DepfileParseOptions::default())
+    }
+
+    /// Tries to parse the depfile `text` with the parse `options`.
+    ///
+    /// Returns an [`error`](DepfileError) with the line of the first invalid rule, if any.
+    pub fn parse_with(text: &str, options: DepfileParseOptions<'_>) -> Result<Self, DepfileError> {
+        let mut parser = Parser {
+            options,
+            rules: Vec::new(),
+            line: 1,
+            rule_line: None,
+            token: String::new(),
+            targets: Vec::new(),
+            deps: Vec::new(),
+            colon: false,
+        };
+
+        let mut idx = 0;
+
+        while idx < text.len() {
+            let rest = &text[idx..];
+
+            // Line continuations.
+            if let Some(len) = ["\\\r\n", "\\\n"]
+                .into_iter()
+                .find_map(|cont| rest.starts_with(cont).then_some(cont.len()))
+            {
+                parser.end_token();
+                parser.line += 1;
+                idx += len;
+                continue;
+            }
+
+            // Escapes.
+            if rest.starts_with("\\ ") || rest.starts_with("\\#") {
+                parser.push(rest.as_bytes()[1] as char);
+                idx += 2;
+                continue;
+            }
+            if rest.starts_with("$$") {
+                parser.push('$');
+                idx += 2;
+                continue;
+            }
+
+            let mut chars = rest.chars();
+            let c = unsafe { chars.next().unwrap_unchecked_dbg_msg("`rest` is non-empty") };
+            idx += c.len_utf8();
+
+            match c {
+                '\n' => {
+                    parser.end_line()?;
+                    parser.line += 1;
+                }
+                '\r' if chars.as_str().starts_with('\n') => {}
+                ' ' | '\t' => parser.end_token(),
+                '#' => {
+                    // Skip the comment, but not the line break.
+                    idx += chars.as_str().find('\n').unwrap_or(chars.as_str().len());
+                }
+                // Colons not followed by whitespace are part of the path (e.g. `C:/foo`).
+                ':' if !parser.colon
+                    && chars
+                        .as_str()
+                        .chars()
+                        .next()
+                        .is_none_or(|c| c.is_ascii_whitespace()) =>
+                {
+                    parser.end_token();
+                    parser.colon = true;
+                    parser.rule_line.get_or_insert(parser.line);
+                }
+                c => parser.push(c),
+            }
+        }
+
+        parser.end_line()?;
+
+        Ok(Self {
+            rules: parser.rules,
+        })
+    }
+}
+
+impl Display for Depfile {
+    /// Formats the [`Depfile`] with one rule per line, and one dependency per (continued) line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for rule in self.rules.iter() {
+            for (idx, target) in rule.targets.iter().enumerate() {
+                if idx > 0 {
+                    ' '.fmt(f)?;
+                }
+                write_escaped(target.as_str(), f)?;
+            }
+            ':'.fmt(f)?;
+
+            for dep in rule
+                .deps
+                .iter()
+                .map(FilePathBuf::as_str)
+                .chain(rule.external_deps.iter().map(String::as_str))
+            {
+                " \\\n  ".fmt(f)?;
+                write_escaped(dep, f)?;
+            }
+            '\n'.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned by [`Depfile::parse`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DepfileError {
+    /// Line number (1-based) of the start of the invalid rule.
+    pub line: usize,
+    pub kind: DepfileErrorKind,
+}
+
+/// The kind of a [`DepfileError`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DepfileErrorKind {
+    /// Rule is missing the `:` separator between the targets and the dependencies.
+    MissingColon,
+    /// Rule has no targets.
+    MissingTarget,
+    /// A target or dependency is not a valid [`FilePath`],
+    /// e.g. a dependency outside of the project root if those are not [`allowed`](DepfileParseOptions::allow_external).
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+}
+
+impl Error for DepfileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            DepfileErrorKind::InvalidPath(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DepfileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use DepfileErrorKind::*;
+
+        write!(f, "depfile line {}: ", self.line)?;
+
+        match &self.kind {
+            MissingColon => "rule is missing the `:` separator".fmt(f),
+            MissingTarget => "rule has no targets".fmt(f),
+            InvalidPath(err) => write!(f, "invalid path: {err}"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    options: DepfileParseOptions<'a>,
+    rules: Vec<DepfileRule>,
+    /// Current line.
+    line: usize,
+    /// Line of the start of the current rule.
+    rule_line: Option<usize>,
+    token: String,
+    targets: Vec<String>,
+    deps: Vec<String>,
+    /// Whether the current rule's `:` separator was parsed.
+    colon: bool,
+}
+
+impl Parser<'_> {
+    fn push(&mut self, c: char) {
+        self.rule_line.get_or_insert(self.line);
+        self.token.push(c);
+    }
+
+    fn end_token(&mut self) {
+        if !self.token.is_empty() {
+            let token = core::mem::take(&mut self.token);
+            if self.colon {
+                self.deps.push(token);
+            } else {
+                self.targets.push(token);
+            }
+        }
+    }
+
+    fn end_line(&mut self) -> Result<(), DepfileError> {
+        self.end_token();
+
+        let colon = core::mem::take(&mut self.colon);
+        let targets = core::mem::take(&mut self.targets);
+        let deps = core::mem::take(&mut self.deps);
+        let Some(line) = self.rule_line.take() else {
+            return Ok(());
+        };
+
+        let error = |kind| DepfileError { line, kind };
+
+        if !colon {
+            return Err(error(DepfileErrorKind::MissingColon));
+        }
+        if targets.is_empty() {
+            return Err(error(DepfileErrorKind::MissingTarget));
+        }
+
+        let root = self.options.root.and_then(raw_path_to_str);
+
+        // Targets outside of the project root are always an error.
+        let mut rule = DepfileRule {
+            targets: targets
+                .into_iter()
+                .map(|target| {
+                    resolve_path(&target, root)
+                        .map_err(|err| error(DepfileErrorKind::InvalidPath(err)))
+                })
+                .collect::<Result<_, _>>()?,
+            ..DepfileRule::default()
+        };
+
+        for dep in deps {
+            match resolve_path(&dep, root) {
+                Ok(dep) => rule.deps.push(dep),
+                Err(err) if self.options.allow_external && is_external(&err) => {
+                    rule.external_deps.push(dep)
+                }
+                Err(err) => return Err(error(DepfileErrorKind::InvalidPath(err))),
+            }
+        }
+
+        self.rules.push(rule);
+        Ok(())
+    }
+}
+
+/// Tries to resolve the target / dependency `path` to a [`FilePathBuf`] relative to the project `root`.
+///
+/// Paths outside of the project root fail with an [`external`](is_external) error.
+fn resolve_path(path: &str, root: Option<&str>) -> Result<FilePathBuf, FilePathError> {
+    let err = match FilePathBuf::new(path) {
+        Ok(path) => return Ok(path),
+        Err(err) => err,
+    };
+
+    let (prefix, rest, external) = match split_absolute(path) {
+        // E.g. `C:/foo` on a non-Windows platform fails with an invalid character error.
+        Some((prefix, rest)) if !is_external(&err) => {
+            (Some(prefix), rest, FilePathError::PrefixedPath)
+        }
+        Some((prefix, rest)) => (Some(prefix), rest, err),
+        None if is_external(&err) => (None, path, err),
+        None => return Err(err),
+    };
+
+    let Some(mut components) = resolve_components(rest) else {
+        return Err(external);
+    };
+
+    if let Some(prefix) = prefix {
+        let Some((root_prefix, root_components)) = root.and_then(|root| {
+            let (root_prefix, root) = split_absolute(root)?;
+            Some((root_prefix, resolve_components(root)?))
+        }) else {
+            return Err(external);
+        };
+
+        if !prefix.eq_ignore_ascii_case(root_prefix) || !components.starts_with(&root_components) {
+            return Err(external);
+        }
+
+        components.drain(..root_components.len());
+    }
+
+    // Fails with `EmptyPath` if the `path` resolves to the project root.
+    FilePathBuf::try_from(components.join("/"))
+}
+
+/// If the `path` is absolute, returns its prefix (empty, or a Windows drive, e.g. `C:`)
+/// and the rest of the `path` after the root separator.
+fn split_absolute(path: &str) -> Option<(&str, &str)> {
+    let is_separator = |c| c == '/' || c == '\\';
+
+    let (prefix, rest) = match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => path.split_at(2),
+        _ => ("", path),
+    };
+
+    rest.strip_prefix(is_separator).map(|rest| (prefix, rest))
+}
+
+/// Splits the `/` or `\` separated (relative) `path` into components,
+/// skipping current directory components and resolving parent directory components.
+///
+/// Returns `None` if the `path` resolves to outside of its base directory.
+fn resolve_components(path: &str) -> Option<Vec<&str>> {
+    let mut components = Vec::new();
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+
+    Some(components)
+}
+
+fn is_external(err: &FilePathError) -> bool {
+    matches!(
+        err,
+        FilePathError::PrefixedPath
+            | FilePathError::RootDirectory
+            | FilePathError::ParentDirectory(_)
+    )
+}
+
+fn write_escaped(path: &str, f: &mut Formatter<'_>) -> core::fmt::Result {
+    for c in path.chars() {
+        match c {
+            ' ' => "\\ ".fmt(f)?,
+            '#' => "\\#".fmt(f)?,
+            '$' => "$$".fmt(f)?,
+            c => c.fmt(f)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<FilePathBuf> {
+        paths
+            .iter()
+            .map(|path| FilePathBuf::new(path).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parse() {
+        let text = "\
+# Generated by the shader compiler.
+out/lit.dxil out/lit.pdb: shaders/lit.hlsl \\
+  shaders/common\\ lib.hlsl shaders/$$cache\\#1.hlsl\\\r
+  shaders//./brdf.hlsl # comment
+
+shaders/lit.hlsl:
+";
+        let depfile = Depfile::parse(text).unwrap();
+        assert_eq!(
+            depfile.rules,
+            [
+                DepfileRule {
+                    targets: paths(&["out/lit.dxil", "out/lit.pdb"]),
+                    deps: paths(&[
+                        "shaders/lit.hlsl",
+                        "shaders/common lib.hlsl",
+                        "shaders/$cache#1.hlsl",
+                        "shaders/brdf.hlsl"
+                    ]),
+                    external_deps: Vec::new(),
+                },
+                DepfileRule {
+                    targets: paths(&["shaders/lit.hlsl"]),
+                    ..DepfileRule::default()
+                }
+            ]
+        );
+
+        let written = depfile.to_string();
+        assert_eq!(
+            written,
+            "\
+out/lit.dxil out/lit.pdb: \\
+  shaders/lit.hlsl \\
+  shaders/common\\ lib.hlsl \\
+  shaders/$$cache\\#1.hlsl \\
+  shaders/brdf.hlsl
+shaders/lit.hlsl:
+"
+        );
+        assert_eq!(Depfile::parse(&written).unwrap(), depfile);
+    }
+
+    #[test]
+    fn external() {
+        let text = "out/lit.dxil: shaders/lit.hlsl /usr/include/stdio.h ../shared/common.hlsl";
+
+        assert_eq!(
+            Depfile::parse(text).err().unwrap(),
+            DepfileError {
+                line: 1,
+                kind: DepfileErrorKind::InvalidPath(FilePathError::RootDirectory)
+            }
+        );
+
+        let options = DepfileParseOptions {
+            allow_external: true,
+            ..DepfileParseOptions::default()
+        };
+        let depfile = Depfile::parse_with(text, options).unwrap();
+        assert_eq!(
+            depfile.rules,
+            [DepfileRule {
+                targets: paths(&["out/lit.dxil"]),
+                deps: paths(&["shaders/lit.hlsl"]),
+                external_deps: vec![
+                    "/usr/include/stdio.h".to_owned(),
+                    "../shared/common.hlsl".to_owned()
+                ],
+            }]
+        );
+        assert_eq!(
+            Depfile::parse_with(&depfile.to_string(), options).unwrap(),
+            depfile
+        );
+
+        // Parent directory components are resolved.
+        assert_eq!(
+            Depfile::parse("a: b/../c d/./e/../../f").unwrap().rules[0].deps,
+            paths(&["c", "f"])
+        );
+        assert_eq!(
+            Depfile::parse("a: b/../../c").err().unwrap(),
+            DepfileError {
+                line: 1,
+                kind: DepfileErrorKind::InvalidPath(FilePathError::ParentDirectory(
+                    ErrorPath::from("b")
+                ))
+            }
+        );
+        assert_eq!(
+            Depfile::parse("a: b/..").err().unwrap(),
+            DepfileError {
+                line: 1,
+                kind: DepfileErrorKind::InvalidPath(FilePathError::EmptyPath)
+            }
+        );
+    }
+
+    #[test]
+    fn root() {
+        fn root<P: AsRef<RawPath> + ?Sized>(root: &P) -> DepfileParseOptions<'_> {
+            DepfileParseOptions {
+                allow_external: true,
+                root: Some(root.as_ref()),
+            }
+        }
+
+        let depfile = Depfile::parse_with(
+            "/home/me/project/out/lit.dxil: /home/me/project/shaders/lit.hlsl \\
+  /home/me/project/shaders/../common//brdf.hlsl \\
+  /home/me/project/../shared/common.hlsl /usr/include/stdio.h C:/project/foo.h",
+            root("/home/me/project/"),
+        )
+        .unwrap();
+        assert_eq!(
+            depfile.rules,
+            [DepfileRule {
+                targets: paths(&["out/lit.dxil"]),
+                deps: paths(&["shaders/lit.hlsl", "common/brdf.hlsl"]),
+                external_deps: vec![
+                    "/home/me/project/../shared/common.hlsl".to_owned(),
+                    "/usr/include/stdio.h".to_owned(),
+                    "C:/project/foo.h".to_owned()
+                ],
+            }]
+        );
+
+        // Targets outside of the project root are an error, even if external dependencies are allowed.
+        assert_eq!(
+            Depfile::parse_with("/home/me/out/lit.dxil: a", root("/home/me/project"))
+                .err()
+                .unwrap(),
+            DepfileError {
+                line: 1,
+                kind: DepfileErrorKind::InvalidPath(FilePathError::RootDirectory)
+            }
+        );
+
+        let depfile = Depfile::parse_with(
+            "out/lit.dxil: C:\\project\\shaders\\lit.hlsl \\
+  c:/project/shaders/../common/brdf.hlsl \\
+  C:\\shared\\common.hlsl D:\\project\\foo.h /project/bar.h",
+            root("C:\\project"),
+        )
+        .unwrap();
+        assert_eq!(
+            depfile.rules[0].deps,
+            paths(&["shaders/lit.hlsl", "common/brdf.hlsl"])
+        );
+        assert_eq!(
+            depfile.rules[0].external_deps,
+            [
+                "C:\\shared\\common.hlsl",
+                "D:\\project\\foo.h",
+                "/project/bar.h"
+            ]
+        );
+
+        // Absolute dependencies are external without a root, even with a drive prefix on non-Windows platforms.
+        assert_eq!(
+            Depfile::parse("a: C:/project/foo.h").err().unwrap(),
+            DepfileError {
+                line: 1,
+                kind: DepfileErrorKind::InvalidPath(FilePathError::PrefixedPath)
+            }
+        );
+        assert_eq!(
+            Depfile::parse_with(
+                "a: C:/project/foo.h",
+                DepfileParseOptions {
+                    allow_external: true,
+                    root: None
+                }
+            )
+            .unwrap()
+            .rules[0]
+                .external_deps,
+            ["C:/project/foo.h"]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let error = |text, line, kind| {
+            assert_eq!(
+                Depfile::parse(text).err().unwrap(),
+                DepfileError { line, kind }
+            );
+        };
+
+        error("a: b\n\nc d", 3, DepfileErrorKind::MissingColon);
+        error("a: b\n: c", 2, DepfileErrorKind::MissingTarget);
+        error(
+            "a: b\nc: \\\n  d e/*.h",
+            2,
+            DepfileErrorKind::InvalidPath(FilePathError::InvalidCharacter((
+                ErrorPath::from("e/*.h"),
+                '*',
+            ))),
+        );
+    }
+}
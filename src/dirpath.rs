@@ -0,0 +1,367 @@
+use {
+    crate::*,
+    core::{
+        borrow::Borrow,
+        cmp::{Ord, Ordering, PartialOrd},
+        fmt::{Display, Formatter},
+        hash::{Hash, Hasher},
+        ops::Deref,
+    },
+    ministr::NonEmptyStr,
+};
+
+/// Non-empty, relative, case agnostic UTF-8 directory path.
+///
+/// Same as a [`FilePath`] (i.e. has the same validation rules and may be non-canonical),
+/// but denotes a directory rather than a file, so the two cannot be mixed up.
+/// Joining is typed: a [`DirPath`] joined with a [`FileName`] / [`FilePath`] results in a [`FilePathBuf`],
+/// and joined with another [`DirPath`] results in a [`DirPathBuf`].
+///
+/// NOTE: a trailing path separator is ignored by [`new`](Self::new). Also see [`parse_path`],
+/// which parses paths with a trailing separator as directory paths.
+///
+/// This is the borrowed version, [`DirPathBuf`] is the owned version.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct DirPath(FilePath);
+
+impl DirPath {
+    /// Tries to create a [`DirPath`] directly from a [`path`](RawPath).
+    ///
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`DirPath`].
+    pub fn new<P: AsRef<RawPath> + ?Sized>(path: &P) -> Result<&Self, FilePathError> {
+        FilePath::new(path).map(Self::from_file_path)
+    }
+
+    /// Returns the length in bytes of the [`DirPath`]. Always > 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the [`DirPath`] as an untyped [`FilePath`].
+    pub fn as_file_path(&self) -> &FilePath {
+        &self.0
+    }
+
+    #[cfg(feature = "std")]
+    pub fn as_path(&self) -> &std::path::Path {
+        self.0.as_path()
+    }
+
+    pub fn as_ne_str(&self) -> &NonEmptyStr {
+        self.0.as_ne_str()
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns an [`iterator`](FilePathIter) over the [`file name`](FileName) components of the [`DirPath`], root to leaf.
+    pub fn components(&self) -> FilePathIter<'_> {
+        self.0.components()
+    }
+
+    /// Returns the name of the directory (i.e. the last/leaf component).
+    pub fn dir_name(&self) -> &FileName {
+        self.0.file_name()
+    }
+
+    /// Returns `true` if the [`DirPath`] starts with all the components of the `base` [`DirPath`] (compared componentwise).
+    ///
+    /// NOTE: a [`DirPath`] starts with itself.
+    pub fn starts_with(&self, base: &DirPath) -> bool {
+        self.0.starts_with(&base.0)
+    }
+
+    /// Returns `true` if the `path` is located (maybe indirectly) in this directory.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{DirPath, FilePath};
+    ///
+    /// let textures = DirPath::new("textures").unwrap();
+    /// assert!(textures.contains(FilePath::new("textures/props/barrel.png").unwrap()));
+    /// assert!(!textures.contains(FilePath::new("textures").unwrap()));
+    /// ```
+    pub fn contains(&self, path: &FilePath) -> bool {
+        let mut components = path.components();
+        self.components()
+            .all(|dir| components.next().is_some_and(|component| component == dir))
+            && components.next().is_some()
+    }
+
+    /// Tries to join the [`DirPath`] with the `file_name`, returning the (canonical) path to the file.
+    ///
+    /// Returns an [`error`](FilePathError) if the resulting path would be too long.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{DirPath, FileName};
+    ///
+    /// let path = DirPath::new("textures//props/").unwrap().join(FileName::new("barrel.png").unwrap()).unwrap();
+    /// assert_eq!(path.as_str(), "textures/props/barrel.png");
+    /// ```
+    pub fn join(&self, file_name: &FileName) -> Result<FilePathBuf, FilePathError> {
+        let mut builder = self.to_builder(file_name.len());
+        builder.push_component(file_name)?;
+        Ok(Self::build(builder))
+    }
+
+    /// Tries to join the [`DirPath`] with the relative file `path`, returning the (canonical) path to the file.
+    ///
+    /// Returns an [`error`](FilePathError) if the resulting path would be too long.
+    pub fn join_path(&self, path: &FilePath) -> Result<FilePathBuf, FilePathError> {
+        let mut builder = self.to_builder(path.len());
+        builder.try_extend(path.components())?;
+        Ok(Self::build(builder))
+    }
+
+    /// Tries to join the [`DirPath`] with the relative directory path `dir`, returning the (canonical) path to the directory.
+    ///
+    /// Returns an [`error`](FilePathError) if the resulting path would be too long.
+    pub fn join_dir(&self, dir: &DirPath) -> Result<DirPathBuf, FilePathError> {
+        self.join_path(&dir.0).map(DirPathBuf)
+    }
+
+    /// Returns the [`DirPath`] of a single component directory `name`.
+    pub fn from_file_name(name: &FileName) -> &Self {
+        Self::from_file_path(name.as_file_path())
+    }
+
+    fn from_file_path(path: &FilePath) -> &Self {
+        // `DirPath` is a `#[repr(transparent)]` wrapper around a `FilePath`.
+        unsafe { &*(path as *const FilePath as *const DirPath) }
+    }
+
+    fn to_builder(&self, additional: usize) -> FilePathBuilder {
+        let mut builder = FilePathBuilder::with_capacity(self.len() + 1 + additional);
+        // Valid components of a path which is not too long.
+        unsafe {
+            builder
+                .try_extend(self.components())
+                .ok()
+                .unwrap_unchecked_dbg_msg("`DirPath`'s must be valid")
+        };
+        builder
+    }
+
+    fn build(builder: FilePathBuilder) -> FilePathBuf {
+        unsafe {
+            builder
+                .build()
+                .unwrap_unchecked_dbg_msg("joined paths must be non-empty")
+        }
+    }
+}
+
+impl AsRef<DirPath> for DirPath {
+    fn as_ref(&self) -> &DirPath {
+        self
+    }
+}
+
+impl ToOwned for DirPath {
+    type Owned = DirPathBuf;
+
+    fn to_owned(&self) -> Self::Owned {
+        DirPathBuf(self.0.to_owned())
+    }
+}
+
+impl PartialEq for DirPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for DirPath {}
+
+impl PartialOrd for DirPath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DirPath {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for DirPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl Display for DirPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Non-empty, relative, case agnostic UTF-8 directory path.
+///
+/// Same as a [`FilePathBuf`] (i.e. uses the canonical string representation, without a trailing separator),
+/// but denotes a directory rather than a file. See [`DirPath`].
+///
+/// This is the owned version, [`DirPath`] is the borrowed version.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct DirPathBuf(FilePathBuf);
+
+impl DirPathBuf {
+    /// Tries to create a [`DirPathBuf`] directly from a [`path`](RawPath).
+    ///
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`DirPathBuf`].
+    pub fn new<P: AsRef<RawPath>>(path: P) -> Result<Self, FilePathError> {
+        FilePathBuf::new(path).map(Self)
+    }
+
+    /// Reinterprets the (untyped) `path` as a directory path.
+    pub fn from_file_path_buf(path: FilePathBuf) -> Self {
+        Self(path)
+    }
+
+    pub fn as_dir_path(&self) -> &DirPath {
+        DirPath::from_file_path(self.0.as_file_path())
+    }
+
+    /// Converts the [`DirPathBuf`] to an untyped [`FilePathBuf`].
+    pub fn into_file_path_buf(self) -> FilePathBuf {
+        self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0.into_string()
+    }
+
+    /// Returns an [`iterator`](FilePathBufIter) over the [`file name`](FileName) components of the [`DirPathBuf`], root to leaf.
+    pub fn components(&self) -> FilePathBufIter<'_> {
+        self.0.components()
+    }
+}
+
+impl Deref for DirPathBuf {
+    type Target = DirPath;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_dir_path()
+    }
+}
+
+impl AsRef<DirPath> for DirPathBuf {
+    fn as_ref(&self) -> &DirPath {
+        self.as_dir_path()
+    }
+}
+
+impl Borrow<DirPath> for DirPathBuf {
+    fn borrow(&self) -> &DirPath {
+        self.as_dir_path()
+    }
+}
+
+impl From<&DirPath> for DirPathBuf {
+    fn from(path: &DirPath) -> Self {
+        path.to_owned()
+    }
+}
+
+impl Display for DirPathBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A path parsed by [`parse_path`] - either a file or a directory path.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ParsedPath<'a> {
+    File(&'a FilePath),
+    Dir(&'a DirPath),
+}
+
+/// Tries to parse the `path` as a [`FilePath`], or as a [`DirPath`] if it ends with a trailing path separator.
+///
+/// Returns an [`error`](FilePathError) if the `path` is not a valid [`FilePath`] / [`DirPath`].
+///
+/// E.g.
+/// ```
+/// use minifilepath::{parse_path, ParsedPath};
+///
+/// assert!(matches!(parse_path("textures/props").unwrap(), ParsedPath::File(_)));
+/// assert!(matches!(parse_path("textures/props/").unwrap(), ParsedPath::Dir(_)));
+/// ```
+pub fn parse_path<P: AsRef<RawPath> + ?Sized>(path: &P) -> Result<ParsedPath<'_>, FilePathError> {
+    let file_path = FilePath::new(path)?;
+
+    if file_path.as_str().ends_with(is_separator) {
+        Ok(ParsedPath::Dir(DirPath::from_file_path(file_path)))
+    } else {
+        Ok(ParsedPath::File(file_path))
+    }
+}
+
+#[cfg(feature = "std")]
+fn is_separator(c: char) -> bool {
+    std::path::is_separator(c)
+}
+
+#[cfg(not(feature = "std"))]
+fn is_separator(c: char) -> bool {
+    c == SEPARATOR_CHAR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join() {
+        let dir = DirPath::new("textures//props/").unwrap();
+        assert_eq!(dir.as_str(), "textures//props/");
+        assert_eq!(dir.dir_name(), "props");
+
+        let file = dir.join(FileName::new("barrel.png").unwrap()).unwrap();
+        assert_eq!(file.as_str(), "textures/props/barrel.png");
+        assert!(dir.contains(&file));
+        assert!(!dir.contains(dir.as_file_path()));
+
+        let file = dir
+            .join_path(FilePath::new("barrels/./red.png").unwrap())
+            .unwrap();
+        assert_eq!(file.as_str(), "textures/props/barrels/red.png");
+
+        let sub_dir = dir
+            .join_dir(DirPath::from_file_name(FileName::new("barrels").unwrap()))
+            .unwrap();
+        assert_eq!(sub_dir.as_str(), "textures/props/barrels");
+        assert!(sub_dir.starts_with(dir));
+        assert_eq!(sub_dir, DirPathBuf::new("textures/props/barrels/").unwrap());
+        assert_eq!(*sub_dir, *DirPath::new("textures//props/barrels").unwrap());
+
+        let long_dir = DirPathBuf::new("a".repeat(MAX_COMPONENT_LEN)).unwrap();
+        let mut path = long_dir.clone();
+        while path.len() + long_dir.len() < MAX_PATH_LEN {
+            path = path.join_dir(&long_dir).unwrap();
+        }
+        assert_eq!(
+            path.join_path(long_dir.as_file_path()).err().unwrap(),
+            FilePathError::PathTooLong(path.len() + long_dir.len() + 1)
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_path("foo/bar").unwrap(),
+            ParsedPath::File(FilePath::new("foo/bar").unwrap())
+        );
+        assert_eq!(
+            parse_path("foo//bar//").unwrap(),
+            ParsedPath::Dir(DirPath::new("foo/bar").unwrap())
+        );
+        assert_eq!(parse_path("/").err().unwrap(), FilePathError::RootDirectory);
+        assert_eq!(parse_path("").err().unwrap(), FilePathError::EmptyPath);
+    }
+}
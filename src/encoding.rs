@@ -0,0 +1,404 @@
+use {
+    crate::*,
+    core::{
+        error::Error,
+        fmt::{Display, Formatter},
+        iter::FusedIterator,
+    },
+    ministr::{NonEmptyStr, NonEmptyString},
+};
+
+/// Size in bytes of the little-endian `u16` path length prefix of the encoded [`FilePath`]'s.
+pub const ENCODED_LEN_PREFIX_SIZE: usize = 2;
+
+/// An error returned when decoding the binary encoded [`FilePath`]'s.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// The input ended before the end of the encoded path.
+    UnexpectedEnd,
+    /// The decoded path is not a valid, canonical [`FilePath`].
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::UnexpectedEnd => None,
+            DecodeError::InvalidPath(err) => Some(err),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => "unexpected end of the encoded path".fmt(f),
+            DecodeError::InvalidPath(err) => write!(f, "invalid encoded path: {err}"),
+        }
+    }
+}
+
+impl From<FilePathError> for DecodeError {
+    fn from(err: FilePathError) -> Self {
+        DecodeError::InvalidPath(err)
+    }
+}
+
+impl FilePath {
+    /// Tries to decode a [`FilePath`] [`encoded`](AsFilePath::encode) at the start of the `bytes`, without copying it.
+    ///
+    /// Returns the [`FilePath`] (which always uses the canonical string representation) and the rest of the `bytes`.
+    ///
+    /// Returns an [`error`](DecodeError) if the `bytes` are too short or do not contain a valid canonical [`FilePath`].
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{AsFilePath, FilePath};
+    ///
+    /// let mut buf = Vec::new();
+    /// FilePath::new("textures//props/barrel.png").unwrap().encode(&mut buf);
+    /// buf.push(7);
+    ///
+    /// let (path, rest) = FilePath::decode(&buf).unwrap();
+    /// assert_eq!(path.as_str(), "textures/props/barrel.png");
+    /// assert_eq!(rest, &[7]);
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<(&FilePath, &[u8]), DecodeError> {
+        let (path, rest) = split_encoded(bytes, 0)?;
+
+        let path =
+            str::from_utf8(path).map_err(|_| FilePathError::InvalidUTF8(ErrorPath::new()))?;
+        let file_path = FilePath::new(path)?;
+        if !file_path.is_canonical() {
+            return Err(FilePathError::NonCanonicalPath(ErrorPath::from(path)).into());
+        }
+
+        Ok((file_path, rest))
+    }
+
+    /// Decodes a [`FilePath`] [`encoded`](AsFilePath::encode) at the start of the `bytes`, without copying or validating it.
+    ///
+    /// Returns the [`FilePath`] and the rest of the `bytes`.
+    ///
+    /// # Safety
+    ///
+    /// The caller guarantees the `bytes` start with a valid [`encoded`](AsFilePath::encode) [`FilePath`]
+    /// (e.g. the `bytes` were previously produced by [`encode`](AsFilePath::encode) and validated).
+    ///
+    /// # Panics
+    ///
+    /// In debug configuration only, panics if the `bytes` do not start with a valid encoded [`FilePath`].
+    pub unsafe fn decode_unchecked(bytes: &[u8]) -> (&FilePath, &[u8]) {
+        debug_assert!(
+            Self::decode(bytes).is_ok(),
+            "tried to decode an invalid encoded `FilePath`"
+        );
+        let (path, rest) = unsafe { split_encoded_unchecked(bytes, 0) };
+        (
+            unsafe {
+                FilePath::from_str(NonEmptyStr::new_unchecked(str::from_utf8_unchecked(path)))
+            },
+            rest,
+        )
+    }
+}
+
+/// Validated, zero-copy view of a [`FilePath`] [`encoded`](AsFilePath::encode_components) using the per-component layout.
+///
+/// Is an [`Iterator`] over the [`file name`](FileName) components of the path, root to leaf.
+#[derive(Clone, Copy, Debug)]
+pub struct EncodedComponents<'a> {
+    /// Remaining encoded components, each preceded by its `u8` length.
+    bytes: &'a [u8],
+}
+
+impl<'a> EncodedComponents<'a> {
+    /// Tries to decode a [`FilePath`] [`encoded`](AsFilePath::encode_components) at the start of the `bytes`, without copying it.
+    ///
+    /// Returns the validated path components and the rest of the `bytes`.
+    ///
+    /// Returns an [`error`](DecodeError) if the `bytes` are too short or do not contain a valid encoded [`FilePath`].
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{AsFilePath, EncodedComponents, FilePath};
+    ///
+    /// let mut buf = Vec::new();
+    /// FilePath::new("textures/props/barrel.png").unwrap().encode_components(&mut buf);
+    ///
+    /// let (components, rest) = EncodedComponents::decode(&buf).unwrap();
+    /// assert!(components.eq(["textures", "props", "barrel.png"]));
+    /// assert!(rest.is_empty());
+    /// ```
+    pub fn decode(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), DecodeError> {
+        // Count the length prefix of the first component.
+        let (mut components, rest) = split_encoded(bytes, 1)?;
+        let encoded = components;
+
+        while let Some((&len, rest)) = components.split_first() {
+            // Path to the parent of the current component, from the components validated so far.
+            let parent_path = || {
+                let mut path = ErrorPath::new();
+                let validated = Self {
+                    bytes: &encoded[..encoded.len() - components.len()],
+                };
+                for component in validated {
+                    push_error_path(&mut path, component.as_str());
+                }
+                path
+            };
+
+            let component = rest
+                .get(..len as usize)
+                // Component overruns the encoded path.
+                .ok_or(DecodeError::UnexpectedEnd)
+                .map(str::from_utf8)?
+                .map_err(|_| FilePathError::InvalidUTF8(parent_path()))?;
+            let component = NonEmptyStr::new(component)
+                .ok_or_else(|| FilePathError::EmptyComponent(parent_path()))?;
+            validate_normal_path_component(component, parent_path)?;
+            components = &rest[len as usize..];
+        }
+
+        Ok((Self { bytes: encoded }, rest))
+    }
+
+    /// Decodes a [`FilePath`] [`encoded`](AsFilePath::encode_components) at the start of the `bytes`, without copying or validating it.
+    ///
+    /// Returns the path components and the rest of the `bytes`.
+    ///
+    /// # Safety
+    ///
+    /// The caller guarantees the `bytes` start with a valid [`encoded`](AsFilePath::encode_components) [`FilePath`]
+    /// (e.g. the `bytes` were previously produced by [`encode_components`](AsFilePath::encode_components) and validated).
+    ///
+    /// # Panics
+    ///
+    /// In debug configuration only, panics if the `bytes` do not start with a valid encoded [`FilePath`].
+    pub unsafe fn decode_unchecked(bytes: &'a [u8]) -> (Self, &'a [u8]) {
+        debug_assert!(
+            Self::decode(bytes).is_ok(),
+            "tried to decode an invalid encoded `FilePath`"
+        );
+        let (bytes, rest) = unsafe { split_encoded_unchecked(bytes, 1) };
+        (Self { bytes }, rest)
+    }
+
+    /// Returns the length in bytes of the canonical string representation of the (remaining) path.
+    /// Zero if all components were iterated.
+    pub fn len(&self) -> usize {
+        self.bytes.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Creates the (canonical) [`FilePathBuf`] from the (remaining) path components.
+    ///
+    /// Returns `None` if all components were iterated.
+    pub fn to_file_path_buf(&self) -> Option<FilePathBuf> {
+        let mut string = String::with_capacity(self.len());
+
+        for component in *self {
+            if !string.is_empty() {
+                string.push(SEPARATOR_CHAR);
+            }
+            string.push_str(component);
+        }
+
+        NonEmptyString::new(string).map(FilePathBuf)
+    }
+}
+
+impl<'a> Iterator for EncodedComponents<'a> {
+    type Item = &'a FileName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&len, rest) = self.bytes.split_first()?;
+        let (component, rest) = rest.split_at(len as usize);
+        self.bytes = rest;
+        // Only contains valid components.
+        Some(unsafe { FileName::new_unchecked(str::from_utf8_unchecked(component)) })
+    }
+}
+
+impl<'a> FusedIterator for EncodedComponents<'a> {}
+
+/// Same as [`AsFilePath::encode`], but for a non-empty sequence of path `components`
+/// which is not too long.
+pub(crate) fn encode_path<'a, I: Iterator<Item = &'a FileName>>(components: I, buf: &mut Vec<u8>) {
+    encode_impl(components, buf, |buf, idx, component| {
+        if idx > 0 {
+            buf.push(SEPARATOR_BYTE);
+        }
+        buf.extend_from_slice(component.as_bytes());
+    })
+}
+
+/// Same as [`AsFilePath::encode_components`], but for a non-empty sequence of path `components`
+/// which is not too long.
+pub(crate) fn encode_path_components<'a, I: Iterator<Item = &'a FileName>>(
+    components: I,
+    buf: &mut Vec<u8>,
+) {
+    encode_impl(components, buf, |buf, _, component| {
+        // `MAX_COMPONENT_LEN == u8::MAX`.
+        buf.push(component.len() as u8);
+        buf.extend_from_slice(component.as_bytes());
+    })
+}
+
+fn encode_impl<'a, I: Iterator<Item = &'a FileName>, F: FnMut(&mut Vec<u8>, usize, &FileName)>(
+    components: I,
+    buf: &mut Vec<u8>,
+    mut f: F,
+) {
+    let start = buf.len();
+    buf.extend_from_slice(&[0; ENCODED_LEN_PREFIX_SIZE]);
+
+    let mut len = 0;
+    for (idx, component) in components.enumerate() {
+        // Count the separator.
+        len += if idx > 0 { 1 } else { 0 } + component.len();
+        f(buf, idx, component);
+    }
+
+    // `MAX_PATH_LEN == u16::MAX`.
+    debug_assert!(len <= MAX_PATH_LEN);
+    buf[start..start + ENCODED_LEN_PREFIX_SIZE].copy_from_slice(&(len as u16).to_le_bytes());
+}
+
+/// Splits the `bytes` into the encoded path of the prefixed length plus `extra` bytes, and the rest of the `bytes`.
+fn split_encoded(bytes: &[u8], extra: usize) -> Result<(&[u8], &[u8]), DecodeError> {
+    let (len, rest) = bytes
+        .split_first_chunk::<ENCODED_LEN_PREFIX_SIZE>()
+        .ok_or(DecodeError::UnexpectedEnd)?;
+    let len = u16::from_le_bytes(*len) as usize;
+    if len == 0 {
+        return Err(FilePathError::EmptyPath.into());
+    }
+    rest.split_at_checked(len + extra)
+        .ok_or(DecodeError::UnexpectedEnd)
+}
+
+unsafe fn split_encoded_unchecked(bytes: &[u8], extra: usize) -> (&[u8], &[u8]) {
+    unsafe {
+        let (len, rest) = bytes
+            .split_first_chunk::<ENCODED_LEN_PREFIX_SIZE>()
+            .unwrap_unchecked_dbg_msg("invalid encoded `FilePath`");
+        let len = u16::from_le_bytes(*len) as usize;
+        rest.split_at_checked(len + extra)
+            .unwrap_unchecked_dbg_msg("invalid encoded `FilePath`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let path = FilePath::new("foo//bar/./baz.txt").unwrap();
+        let mut buf = vec![7];
+        path.encode(&mut buf);
+        assert_eq!(&buf[..3], &[7, 15, 0]);
+        assert_eq!(&buf[3..], b"foo/bar/baz.txt");
+        buf.push(9);
+
+        let (decoded, rest) = FilePath::decode(&buf[1..]).unwrap();
+        assert_eq!(decoded.as_str(), "foo/bar/baz.txt");
+        assert_eq!(rest, &[9]);
+        let (decoded, rest) = unsafe { FilePath::decode_unchecked(&buf[1..]) };
+        assert_eq!(decoded, path);
+        assert_eq!(rest, &[9]);
+
+        assert_eq!(
+            FilePath::decode(&buf[1..buf.len() - 2]).err().unwrap(),
+            DecodeError::UnexpectedEnd
+        );
+        assert_eq!(
+            FilePath::decode(&[15]).err().unwrap(),
+            DecodeError::UnexpectedEnd
+        );
+        assert_eq!(
+            FilePath::decode(&[0, 0]).err().unwrap(),
+            DecodeError::InvalidPath(FilePathError::EmptyPath)
+        );
+        assert_eq!(
+            FilePath::decode(b"\x04\x00a//b").err().unwrap(),
+            DecodeError::InvalidPath(FilePathError::NonCanonicalPath(ErrorPath::from("a//b")))
+        );
+        assert_eq!(
+            FilePath::decode(b"\x04\x00/foo").err().unwrap(),
+            DecodeError::InvalidPath(FilePathError::RootDirectory)
+        );
+        assert!(matches!(
+            FilePath::decode(b"\x02\x00\xff\xfe").err().unwrap(),
+            DecodeError::InvalidPath(FilePathError::InvalidUTF8(_))
+        ));
+    }
+
+    #[test]
+    fn encode_components() {
+        let path = FilePath::new("foo//bar/./baz.txt").unwrap();
+        let mut buf = Vec::new();
+        path.encode_components(&mut buf);
+        assert_eq!(buf.len(), 2 + 15 + 1);
+        assert_eq!(&buf[..2], &[15, 0]);
+        assert_eq!(&buf[2..], b"\x03foo\x03bar\x07baz.txt");
+        buf.push(9);
+
+        let (components, rest) = EncodedComponents::decode(&buf).unwrap();
+        assert_eq!(rest, &[9]);
+        assert_eq!(components.len(), 15);
+        assert!(components.eq(["foo", "bar", "baz.txt"]));
+        assert_eq!(
+            components.to_file_path_buf().unwrap().as_str(),
+            "foo/bar/baz.txt"
+        );
+        let mut components = unsafe { EncodedComponents::decode_unchecked(&buf).0 };
+        assert_eq!(components.next().unwrap(), "foo");
+        assert_eq!(
+            components.to_file_path_buf().unwrap().as_str(),
+            "bar/baz.txt"
+        );
+        assert_eq!(components.count(), 2);
+
+        assert_eq!(
+            EncodedComponents::decode(&buf[..buf.len() - 2])
+                .err()
+                .unwrap(),
+            DecodeError::UnexpectedEnd
+        );
+        assert_eq!(
+            EncodedComponents::decode(b"\x03\x00\x01a\x00\x00")
+                .err()
+                .unwrap(),
+            DecodeError::InvalidPath(FilePathError::EmptyComponent(ErrorPath::from("a")))
+        );
+        assert_eq!(
+            EncodedComponents::decode(b"\x03\x00\x01a\x01.")
+                .err()
+                .unwrap(),
+            DecodeError::InvalidPath(FilePathError::CurrentDirectory(ErrorPath::from("a")))
+        );
+        assert_eq!(
+            EncodedComponents::decode(b"\x05\x00\x01a\x01b\x01:")
+                .err()
+                .unwrap(),
+            DecodeError::InvalidPath(FilePathError::InvalidCharacter((
+                ErrorPath::from("a/b/:"),
+                ':'
+            )))
+        );
+        // Component overruns the encoded path.
+        assert_eq!(
+            EncodedComponents::decode(b"\x03\x00\x01a\x05b")
+                .err()
+                .unwrap(),
+            DecodeError::UnexpectedEnd
+        );
+    }
+}
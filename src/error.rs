@@ -1,12 +1,21 @@
 use {
     crate::MAX_PATH_LEN,
-    std::{
+    core::{
         error::Error,
         fmt::{Display, Formatter},
-        path::PathBuf,
     },
 };
 
+/// The path to an invalid path component, contained in [`FilePathError`]'s:
+/// [`std::path::PathBuf`] with the `std` feature, or a `String` otherwise.
+#[cfg(feature = "std")]
+pub type ErrorPath = std::path::PathBuf;
+
+/// The path to an invalid path component, contained in [`FilePathError`]'s:
+/// [`std::path::PathBuf`] with the `std` feature, or a `String` otherwise.
+#[cfg(not(feature = "std"))]
+pub type ErrorPath = alloc::string::String;
+
 /// An error returned by methods which construct [`FilePath`](crate::FilePath)'s / [`FilePathBuf`](crate::FilePathBuf)'s.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum FilePathError {
@@ -16,42 +25,51 @@ pub enum FilePathError {
     RootDirectory,
     /// Path contains a current directory component.
     /// Contains the path to the invalid component.
-    CurrentDirectory(PathBuf),
+    CurrentDirectory(ErrorPath),
     /// Path contains a parent directory component.
     /// Contains the path to the invalid component.
-    ParentDirectory(PathBuf),
+    ParentDirectory(ErrorPath),
     /// A path component is empty.
     /// Contains the path to the empty component.
-    EmptyComponent(PathBuf),
+    EmptyComponent(ErrorPath),
     /// A path component length in bytes is longer than `MAX_COMPONENT_LEN`.
     /// Contains the path to the component and its length in bytes.
-    ComponentTooLong((PathBuf, usize)),
+    ComponentTooLong((ErrorPath, usize)),
     /// Path component contains an invalid character.
     /// Contains the path to the invalid component and the invalid character.
-    InvalidCharacter((PathBuf, char)),
+    InvalidCharacter((ErrorPath, char)),
     /// Path component ends with a period.
     /// Contains the path to the invalid component.
-    ComponentEndsWithAPeriod(PathBuf),
+    ComponentEndsWithAPeriod(ErrorPath),
     /// Path component ends with a space.
     /// Contains the path to the invalid component.
-    ComponentEndsWithASpace(PathBuf),
+    ComponentEndsWithASpace(ErrorPath),
     /// Path component contains a reserved file name.
     /// Contains the path to the invalid component.
-    ReservedName(PathBuf),
+    ReservedName(ErrorPath),
     /// A path component contains invalid UTF-8.
     /// Contains the path to the invalid component.
-    InvalidUTF8(PathBuf),
+    InvalidUTF8(ErrorPath),
     /// Empty paths are not allowed.
     EmptyPath,
     /// Path length in bytes is longer than `MAX_PATH_LEN`.
     /// Contains the length of the path in bytes.
     PathTooLong(usize),
+    /// Path length in bytes is longer than the capacity of a fixed-capacity buffer.
+    /// Contains the length of the path in bytes.
+    CapacityExceeded(usize),
+    /// File path does not have one of the expected file extensions.
+    /// Contains the path.
+    InvalidExtension(ErrorPath),
+    /// Path is valid, but does not use the canonical string representation where one is required.
+    /// Contains the path.
+    NonCanonicalPath(ErrorPath),
 }
 
 impl Error for FilePathError {}
 
 impl Display for FilePathError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         use FilePathError::*;
 
         match self {
@@ -60,44 +78,44 @@ impl Display for FilePathError {
             CurrentDirectory(path) => write!(
                 f,
                 "path component at \"{}\" contains a current directory component",
-                path.display()
+                display(path)
             ),
             ParentDirectory(path) => write!(
                 f,
                 "path component at \"{}\" contains a parent directory component",
-                path.display()
+                display(path)
             ),
             EmptyComponent(path) => write!(f, "path component at {:?} is empty", path),
             ComponentTooLong((path, len)) => write!(
                 f,
                 "path component at \"{}\" is too long ({} bytes)",
-                path.display(),
+                display(path),
                 len
             ),
             InvalidCharacter((path, c)) => write!(
                 f,
                 "path component at \"{}\" contains an invalid character ('{}')",
-                path.display(),
+                display(path),
                 c
             ),
             ComponentEndsWithAPeriod(path) => {
                 write!(
                     f,
                     "path component at \"{}\" ends with a period",
-                    path.display()
+                    display(path)
                 )
             }
             ComponentEndsWithASpace(path) => {
                 write!(
                     f,
                     "path component at \"{}\" ends with a space",
-                    path.display()
+                    display(path)
                 )
             }
             ReservedName(path) => write!(
                 f,
                 "path component at \"{}\" contains a reserved name",
-                path.display()
+                display(path)
             ),
             InvalidUTF8(path) => {
                 write!(f, "path component at {:?} contains invalid UTF-8", path)
@@ -107,6 +125,30 @@ impl Display for FilePathError {
                 f,
                 "path is too long ({len} bytes, `MAX_PATH_LEN` is {MAX_PATH_LEN})"
             ),
+            CapacityExceeded(len) => write!(
+                f,
+                "path is too long for the fixed-capacity buffer ({len} bytes)"
+            ),
+            InvalidExtension(path) => write!(
+                f,
+                "path \"{}\" does not have an expected extension",
+                display(path)
+            ),
+            NonCanonicalPath(path) => write!(
+                f,
+                "path \"{}\" does not use the canonical string representation",
+                display(path)
+            ),
         }
     }
 }
+
+#[cfg(feature = "std")]
+fn display(path: &ErrorPath) -> impl Display + '_ {
+    path.display()
+}
+
+#[cfg(not(feature = "std"))]
+fn display(path: &ErrorPath) -> impl Display + '_ {
+    path
+}
@@ -0,0 +1,427 @@
+use {
+    crate::*,
+    core::{
+        borrow::Borrow,
+        cmp::{Ord, Ordering, PartialOrd},
+        fmt::{Display, Formatter},
+        hash::{Hash, Hasher},
+        iter::{DoubleEndedIterator, FusedIterator, Iterator},
+        ops::Deref,
+    },
+    ministr::{NonEmptyStr, NonEmptyString},
+};
+
+/// Non-empty, valid UTF-8 file name, i.e. a single [`FilePath`] component.
+///
+/// Validated according to the [`is_valid_path_component`] rules, so contains no path component separators,
+/// is not a current (`"."`) / parent (`".."`) directory component, is not a reserved name, etc.
+///
+/// E.g.: "foo", "Baz BoB.txt", ".gitignore", "archive.tar.gz".
+/// But not "foo/bar", or "..", or "NUL.txt", or "bar.".
+///
+/// This is the borrowed version, [`FileNameBuf`] is the owned version.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct FileName(NonEmptyStr);
+
+impl FileName {
+    /// Tries to create a [`FileName`] directly from a `name` string.
+    ///
+    /// Returns an [`error`](FilePathError) if the `name` is not a valid [`FileName`].
+    pub fn new<S: AsRef<str> + ?Sized>(name: &S) -> Result<&Self, FilePathError> {
+        let name = NonEmptyStr::new(name.as_ref())
+            .ok_or_else(|| FilePathError::EmptyComponent(ErrorPath::new()))?;
+        validate_normal_path_component(name, ErrorPath::new)?;
+        Ok(unsafe { Self::from_ne_str(name) })
+    }
+
+    /// Creates a [`FileName`] directly from a `name` string.
+    ///
+    /// # Safety
+    ///
+    /// The caller guarantees the `name` is a valid [`FileName`].
+    ///
+    /// # Panics
+    ///
+    /// In debug configuration only, panics if `name` is not a valid [`FileName`].
+    pub unsafe fn new_unchecked<S: AsRef<str> + ?Sized>(name: &S) -> &Self {
+        debug_assert!(
+            is_valid_path_component(name.as_ref()),
+            "tried to create a `FileName` from an invalid file name"
+        );
+        unsafe { Self::from_ne_str(NonEmptyStr::new_unchecked(name.as_ref())) }
+    }
+
+    /// Returns the length in bytes of the [`FileName`]. Always > 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the [`FileName`] as a single component [`FilePath`].
+    pub fn as_file_path(&self) -> &FilePath {
+        // A valid file name is a valid (canonical) single component file path.
+        unsafe { FilePath::from_str(&self.0) }
+    }
+
+    pub fn as_ne_str(&self) -> &NonEmptyStr {
+        &self.0
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns the file stem portion of the [`FileName`] (i.e. the non-extension part).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension`].
+    ///
+    /// E.g.
+    /// ```
+    /// use {minifilepath::FileName, ministr_macro::nestr};
+    ///
+    /// assert_eq!(FileName::new("bar.txt").unwrap().file_stem(), Some(nestr!("bar")));
+    /// assert_eq!(FileName::new(".txt").unwrap().file_stem(), None);
+    /// assert_eq!(FileName::new("baz").unwrap().file_stem(), Some(nestr!("baz")));
+    /// ```
+    pub fn file_stem(&self) -> Option<&NonEmptyStr> {
+        file_stem_and_extension(self)
+            .map(|file_stem_and_extension| file_stem_and_extension.file_stem)
+            .unwrap_or(Some(&self.0))
+    }
+
+    /// Returns the extension portion of the [`FileName`] (i.e. the part after the last period).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension`].
+    ///
+    /// E.g.
+    /// ```
+    /// use {minifilepath::FileName, ministr_macro::nestr};
+    ///
+    /// assert_eq!(FileName::new("bar.txt").unwrap().extension(), Some(nestr!("txt")));
+    /// assert_eq!(FileName::new(".txt").unwrap().extension(), Some(nestr!("txt")));
+    /// assert_eq!(FileName::new("baz").unwrap().extension(), None);
+    /// ```
+    pub fn extension(&self) -> Option<&NonEmptyStr> {
+        file_stem_and_extension(self)
+            .map(|file_stem_and_extension| file_stem_and_extension.extension)
+    }
+
+    /// Returns an [`iterator`](FileNameExtensions) over all (non-empty) extensions of the [`FileName`],
+    /// i.e. the parts after the first period, separated by periods, left to right.
+    ///
+    /// NOTE: can be reversed via `rev()` to iterate from the last extension.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::FileName;
+    ///
+    /// let extensions = |name| FileName::new(name).unwrap().extensions().map(|e| e.as_str()).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(extensions("archive.tar.gz"), ["tar", "gz"]);
+    /// assert_eq!(extensions(".gitignore"), ["gitignore"]);
+    /// assert_eq!(extensions("foo..txt"), ["txt"]);
+    /// assert!(extensions("foo").is_empty());
+    /// ```
+    pub fn extensions(&self) -> FileNameExtensions<'_> {
+        let mut extensions = self.as_str().split('.');
+        // Skip the part before the first period.
+        extensions.next();
+        FileNameExtensions(extensions)
+    }
+
+    /// The caller guarantees `name` is a valid file name.
+    pub(crate) unsafe fn from_ne_str(name: &NonEmptyStr) -> &Self {
+        unsafe { &*(name as *const NonEmptyStr as *const FileName) }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a FileName {
+    type Error = FilePathError;
+
+    fn try_from(name: &'a str) -> Result<Self, Self::Error> {
+        FileName::new(name)
+    }
+}
+
+impl Deref for FileName {
+    type Target = NonEmptyStr;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for FileName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<FilePath> for FileName {
+    fn as_ref(&self) -> &FilePath {
+        self.as_file_path()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for FileName {
+    fn as_ref(&self) -> &std::path::Path {
+        std::path::Path::new(self.as_str())
+    }
+}
+
+impl ToOwned for FileName {
+    type Owned = FileNameBuf;
+
+    fn to_owned(&self) -> Self::Owned {
+        FileNameBuf(self.0.to_owned())
+    }
+}
+
+impl PartialEq for FileName {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for FileName {}
+
+impl PartialEq<NonEmptyStr> for FileName {
+    fn eq(&self, other: &NonEmptyStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for FileName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for FileName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for FileName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FileName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for FileName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl Display for FileName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Non-empty, valid UTF-8 file name, i.e. a single [`FilePathBuf`] component.
+///
+/// This is the owned version, [`FileName`] is the borrowed version.
+#[derive(Clone, Debug)]
+pub struct FileNameBuf(NonEmptyString);
+
+impl FileNameBuf {
+    /// Tries to create a [`FileNameBuf`] from a `name` string.
+    ///
+    /// Returns an [`error`](FilePathError) if the `name` is not a valid [`FileName`].
+    pub fn new<S: AsRef<str>>(name: S) -> Result<Self, FilePathError> {
+        FileName::new(name.as_ref()).map(ToOwned::to_owned)
+    }
+
+    /// Creates a [`FileNameBuf`] directly from a `name` string.
+    ///
+    /// # Safety
+    ///
+    /// The caller guarantees the `name` is a valid [`FileName`].
+    ///
+    /// # Panics
+    ///
+    /// In debug configuration only, panics if `name` is not a valid [`FileName`].
+    pub unsafe fn new_unchecked(name: String) -> Self {
+        debug_assert!(
+            is_valid_path_component(&name),
+            "tried to create a `FileNameBuf` from an invalid file name `String`"
+        );
+        Self(unsafe { NonEmptyString::new_unchecked(name) })
+    }
+
+    pub fn as_file_name(&self) -> &FileName {
+        unsafe { FileName::from_ne_str(self.0.as_ne_str()) }
+    }
+
+    pub fn into_ne_string(self) -> NonEmptyString {
+        self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0.into_inner()
+    }
+}
+
+impl TryFrom<String> for FileNameBuf {
+    type Error = FilePathError;
+
+    /// Reuses the `name`'s allocation.
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        FileName::new(&name)?;
+        Ok(Self(unsafe { NonEmptyString::new_unchecked(name) }))
+    }
+}
+
+impl From<&FileName> for FileNameBuf {
+    fn from(name: &FileName) -> Self {
+        name.to_owned()
+    }
+}
+
+impl From<FileNameBuf> for FilePathBuf {
+    fn from(name: FileNameBuf) -> Self {
+        // A valid file name is a valid canonical single component file path.
+        FilePathBuf(name.0)
+    }
+}
+
+impl Deref for FileNameBuf {
+    type Target = FileName;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_file_name()
+    }
+}
+
+impl AsRef<FileName> for FileNameBuf {
+    fn as_ref(&self) -> &FileName {
+        self.as_file_name()
+    }
+}
+
+impl Borrow<FileName> for FileNameBuf {
+    fn borrow(&self) -> &FileName {
+        self.as_file_name()
+    }
+}
+
+impl PartialEq for FileNameBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_file_name() == other.as_file_name()
+    }
+}
+
+impl Eq for FileNameBuf {}
+
+impl PartialOrd for FileNameBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FileNameBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_file_name().cmp(other.as_file_name())
+    }
+}
+
+impl Hash for FileNameBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_file_name().hash(state)
+    }
+}
+
+impl Display for FileNameBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.as_file_name().fmt(f)
+    }
+}
+
+/// Double-ended iterator over the (non-empty) extensions of a [`FileName`].
+///
+/// See [`FileName::extensions`].
+pub struct FileNameExtensions<'a>(core::str::Split<'a, char>);
+
+impl<'a> Iterator for FileNameExtensions<'a> {
+    type Item = &'a NonEmptyStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.by_ref().find_map(NonEmptyStr::new)
+    }
+}
+
+impl<'a> DoubleEndedIterator for FileNameExtensions<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.by_ref().rev().find_map(NonEmptyStr::new)
+    }
+}
+
+impl<'a> FusedIterator for FileNameExtensions<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ministr_macro::nestr};
+
+    #[test]
+    fn file_name() {
+        let name = FileName::new("archive.tar.gz").unwrap();
+        assert_eq!(name, "archive.tar.gz");
+        assert_eq!(name.len(), 14);
+        assert_eq!(name.file_stem(), Some(nestr!("archive.tar")));
+        assert_eq!(name.extension(), Some(nestr!("gz")));
+        assert_eq!(
+            name.extensions()
+                .rev()
+                .map(NonEmptyStr::as_str)
+                .collect::<Vec<_>>(),
+            ["gz", "tar"]
+        );
+        assert_eq!(
+            name.as_file_path(),
+            FilePath::new("archive.tar.gz").unwrap()
+        );
+
+        let name_buf = FileNameBuf::try_from(String::from("archive.tar.gz")).unwrap();
+        assert_eq!(name_buf.as_file_name(), name);
+        assert_eq!(FilePathBuf::from(name_buf).as_str(), "archive.tar.gz");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            FileName::new("").err().unwrap(),
+            FilePathError::EmptyComponent(ErrorPath::new())
+        );
+        assert_eq!(
+            FileName::new(".").err().unwrap(),
+            FilePathError::CurrentDirectory(ErrorPath::new())
+        );
+        assert_eq!(
+            FileName::new("..").err().unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::new())
+        );
+        assert_eq!(
+            FileName::new("a/b").err().unwrap(),
+            FilePathError::InvalidCharacter((ErrorPath::from("a/b"), '/'))
+        );
+        assert_eq!(
+            FileName::new("NUL.txt").err().unwrap(),
+            FilePathError::ReservedName(ErrorPath::from("NUL.txt"))
+        );
+        assert_eq!(
+            FileNameBuf::new("bar.").err().unwrap(),
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::from("bar."))
+        );
+    }
+}
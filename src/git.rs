@@ -0,0 +1,439 @@
+use {
+    crate::*,
+    alloc::borrow::Cow,
+    core::{
+        error::Error,
+        fmt::{Display, Formatter, Write},
+    },
+};
+
+/// Paths of a `diff --git a/<old> b/<new>` header line, see [`parse_diff_git_header`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DiffPaths {
+    pub old: FilePathBuf,
+    pub new: FilePathBuf,
+}
+
+/// A `--- a/<old>` / `+++ b/<new>` header line, see [`parse_diff_file_header`].
+///
+/// Contains `None` for `/dev/null`, i.e. for added / deleted files.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DiffFileHeader {
+    /// `--- a/<old>` header line.
+    Old(Option<FilePathBuf>),
+    /// `+++ b/<new>` header line.
+    New(Option<FilePathBuf>),
+}
+
+/// Tries to parse a (maybe C-quoted, as printed by git) `path` into a [`FilePathBuf`].
+///
+/// Quoted paths are enclosed in double quotes and use C-style escapes (`\\`, `\"`, `\n`, `\t`, etc.),
+/// and octal escapes for (the UTF-8 bytes of) non-ASCII characters; other paths are parsed verbatim.
+///
+/// E.g.
+/// ```
+/// use minifilepath::unquote_git_path;
+///
+/// assert_eq!(unquote_git_path(r#""dir/caf\303\251.txt""#).unwrap().as_str(), "dir/café.txt");
+/// assert_eq!(unquote_git_path("dir/cafe.txt").unwrap().as_str(), "dir/cafe.txt");
+/// ```
+pub fn unquote_git_path(path: &str) -> Result<FilePathBuf, GitPathError> {
+    to_file_path_buf(unquote(path)?)
+}
+
+/// Returns the canonical string representation of the `path` as printed by git (with the default `core.quotePath`):
+/// C-quoted, with octal escapes for the UTF-8 bytes of non-ASCII characters, if the `path` contains any;
+/// verbatim otherwise.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{quote_git_path, FilePath};
+///
+/// assert_eq!(quote_git_path(FilePath::new("dir/café.txt").unwrap()), r#""dir/caf\303\251.txt""#);
+/// assert_eq!(quote_git_path(FilePath::new("dir/cafe.txt").unwrap()), "dir/cafe.txt");
+/// ```
+pub fn quote_git_path(path: &FilePath) -> String {
+    let result = Canonical(path).to_string();
+
+    if !result.bytes().any(needs_quoting) {
+        return result;
+    }
+
+    let mut quoted = String::with_capacity(result.len() + 2);
+    quoted.push('"');
+    for b in result.bytes() {
+        // Invalid characters (control characters, `"`, `\`) are never contained in valid paths;
+        // only non-ASCII bytes need to be escaped.
+        if needs_quoting(b) {
+            write!(quoted, "\\{b:03o}").unwrap();
+        } else {
+            quoted.push(b as char);
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Tries to parse a `diff --git a/<old> b/<new>` header line, where the paths may be C-quoted.
+///
+/// If neither path is quoted and the paths contain `" b/"`, the split is ambiguous,
+/// and only resolved if there's a single split point with equal `<old>` and `<new>` paths (as git does).
+///
+/// E.g.
+/// ```
+/// use minifilepath::parse_diff_git_header;
+///
+/// let paths = parse_diff_git_header(r#"diff --git "a/caf\303\251.txt" b/cafe.txt"#).unwrap();
+/// assert_eq!(paths.old.as_str(), "café.txt");
+/// assert_eq!(paths.new.as_str(), "cafe.txt");
+/// ```
+pub fn parse_diff_git_header(line: &str) -> Result<DiffPaths, GitPathError> {
+    let rest = line
+        .strip_prefix("diff --git ")
+        .ok_or(GitPathError::InvalidHeader)?;
+
+    let mut result = None;
+    let mut ambiguous = false;
+
+    for (idx, _) in rest.match_indices(' ') {
+        let (old, new) = (&rest[..idx], &rest[idx + 1..]);
+
+        if !(new.starts_with("b/") || new.starts_with("\"b/")) {
+            continue;
+        }
+
+        let (Ok(old), Ok(new)) = (unquote(old), unquote(new)) else {
+            continue;
+        };
+        let (Some(old), Some(new)) = (strip_prefix(old, "a/"), strip_prefix(new, "b/")) else {
+            continue;
+        };
+
+        if old == new {
+            // Unambiguous.
+            result = Some((old, new));
+            ambiguous = false;
+            break;
+        } else if result.is_some() {
+            ambiguous = true;
+        } else {
+            result = Some((old, new));
+        }
+    }
+
+    match result {
+        Some((old, new)) if !ambiguous => Ok(DiffPaths {
+            old: to_file_path_buf(old)?,
+            new: to_file_path_buf(new)?,
+        }),
+        _ => Err(GitPathError::InvalidHeader),
+    }
+}
+
+/// Tries to parse a `--- a/<old>` / `+++ b/<new>` (or `--- /dev/null` / `+++ /dev/null`) header line,
+/// where the path may be C-quoted.
+///
+/// Anything after a tab following an unquoted path (e.g. a timestamp) is ignored.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{parse_diff_file_header, DiffFileHeader};
+///
+/// let DiffFileHeader::New(Some(path)) = parse_diff_file_header("+++ b/dir/file name.txt\t").unwrap() else {
+///     panic!()
+/// };
+/// assert_eq!(path.as_str(), "dir/file name.txt");
+/// assert_eq!(parse_diff_file_header("--- /dev/null").unwrap(), DiffFileHeader::Old(None));
+/// ```
+pub fn parse_diff_file_header(line: &str) -> Result<DiffFileHeader, GitPathError> {
+    let (rest, prefix, old) = if let Some(rest) = line.strip_prefix("--- ") {
+        (rest, "a/", true)
+    } else if let Some(rest) = line.strip_prefix("+++ ") {
+        (rest, "b/", false)
+    } else {
+        return Err(GitPathError::InvalidHeader);
+    };
+
+    let path = if rest.starts_with('"') {
+        let (path, rest) = unquote_prefix(rest)?;
+        if !(rest.is_empty() || rest.starts_with('\t')) {
+            return Err(GitPathError::InvalidHeader);
+        }
+        path
+    } else {
+        Cow::Borrowed(rest.split('\t').next().unwrap_or(rest))
+    };
+
+    let path = if path == "/dev/null" {
+        None
+    } else {
+        Some(to_file_path_buf(
+            strip_prefix(path, prefix).ok_or(GitPathError::InvalidHeader)?,
+        )?)
+    };
+
+    Ok(if old {
+        DiffFileHeader::Old(path)
+    } else {
+        DiffFileHeader::New(path)
+    })
+}
+
+/// An error returned when parsing git paths.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GitPathError {
+    /// Quoted path is missing the closing double quote.
+    UnterminatedQuote,
+    /// Quoted path contains an invalid escape sequence.
+    /// Contains the byte offset of the escape sequence in the quoted path.
+    InvalidEscape(usize),
+    /// Diff header line is malformed.
+    InvalidHeader,
+    /// (Unquoted) path is not a valid [`FilePath`].
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+}
+
+impl Error for GitPathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GitPathError::InvalidPath(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GitPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use GitPathError::*;
+
+        match self {
+            UnterminatedQuote => "quoted path is missing the closing quote".fmt(f),
+            InvalidEscape(offset) => write!(f, "invalid escape sequence at offset {offset}"),
+            InvalidHeader => "invalid diff header".fmt(f),
+            InvalidPath(err) => write!(f, "invalid path: {err}"),
+        }
+    }
+}
+
+impl From<FilePathError> for GitPathError {
+    fn from(err: FilePathError) -> Self {
+        GitPathError::InvalidPath(err)
+    }
+}
+
+fn needs_quoting(b: u8) -> bool {
+    !b.is_ascii() || b.is_ascii_control() || b == b'"' || b == b'\\'
+}
+
+fn to_file_path_buf(path: Cow<'_, str>) -> Result<FilePathBuf, GitPathError> {
+    Ok(match path {
+        Cow::Borrowed(path) => FilePathBuf::new(path)?,
+        Cow::Owned(path) => FilePathBuf::try_from(path)?,
+    })
+}
+
+fn strip_prefix<'a>(path: Cow<'a, str>, prefix: &str) -> Option<Cow<'a, str>> {
+    match path {
+        Cow::Borrowed(path) => path.strip_prefix(prefix).map(Cow::Borrowed),
+        Cow::Owned(path) => path
+            .strip_prefix(prefix)
+            .map(|path| Cow::Owned(path.to_owned())),
+    }
+}
+
+/// Unquotes the whole `path` if it is quoted, or returns it verbatim otherwise.
+fn unquote(path: &str) -> Result<Cow<'_, str>, GitPathError> {
+    if path.starts_with('"') {
+        let (path, rest) = unquote_prefix(path)?;
+        if rest.is_empty() {
+            Ok(path)
+        } else {
+            Err(GitPathError::UnterminatedQuote)
+        }
+    } else {
+        Ok(Cow::Borrowed(path))
+    }
+}
+
+/// Unquotes the quoted path at the start of `quoted`, returning the unquoted path and the rest of `quoted`.
+fn unquote_prefix(quoted: &str) -> Result<(Cow<'_, str>, &str), GitPathError> {
+    debug_assert!(quoted.starts_with('"'));
+
+    let bytes = quoted.as_bytes();
+    let mut result = Vec::new();
+    let mut idx = 1;
+
+    loop {
+        let b = *bytes.get(idx).ok_or(GitPathError::UnterminatedQuote)?;
+
+        match b {
+            b'"' => break,
+            b'\\' => {
+                let escape = *bytes.get(idx + 1).ok_or(GitPathError::UnterminatedQuote)?;
+                let unescaped = match escape {
+                    b'a' => 0x07,
+                    b'b' => 0x08,
+                    b't' => b'\t',
+                    b'n' => b'\n',
+                    b'v' => 0x0b,
+                    b'f' => 0x0c,
+                    b'r' => b'\r',
+                    b'"' | b'\\' => escape,
+                    b'0'..=b'3' => {
+                        let digits = bytes
+                            .get(idx + 1..idx + 4)
+                            .filter(|digits| digits.iter().all(|d| (b'0'..=b'7').contains(d)))
+                            .ok_or(GitPathError::InvalidEscape(idx))?;
+                        idx += 2;
+                        digits.iter().fold(0, |acc, d| (acc << 3) | (d - b'0'))
+                    }
+                    _ => return Err(GitPathError::InvalidEscape(idx)),
+                };
+                result.push(unescaped);
+                idx += 2;
+            }
+            _ => {
+                result.push(b);
+                idx += 1;
+            }
+        }
+    }
+
+    let result = String::from_utf8(result).map_err(|err| {
+        FilePathError::InvalidUTF8(ErrorPath::from(
+            String::from_utf8_lossy(err.as_bytes()).as_ref(),
+        ))
+    })?;
+
+    Ok((Cow::Owned(result), &quoted[idx + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote() {
+        for (quoted, path) in [
+            (r#""dir/caf\303\251.txt""#, "dir/café.txt"),
+            (r#""\346\227\245\346\234\254/a b.png""#, "日本/a b.png"),
+            ("dir/cafe.txt", "dir/cafe.txt"),
+        ] {
+            let unquoted = unquote_git_path(quoted).unwrap();
+            assert_eq!(unquoted.as_str(), path);
+            assert_eq!(quote_git_path(&unquoted), quoted);
+        }
+
+        assert_eq!(
+            quote_git_path(FilePath::new("dir//./café.txt").unwrap()),
+            r#""dir/caf\303\251.txt""#
+        );
+        assert_eq!(
+            unquote_git_path(r#""dir/a\tb""#).err().unwrap(),
+            GitPathError::InvalidPath(FilePathError::InvalidCharacter((
+                ErrorPath::from("dir/a\tb"),
+                '\t'
+            )))
+        );
+        assert_eq!(
+            unquote_git_path(r#""dir/a"#).err().unwrap(),
+            GitPathError::UnterminatedQuote
+        );
+        assert_eq!(
+            unquote_git_path(r#""dir/a"b"#).err().unwrap(),
+            GitPathError::UnterminatedQuote
+        );
+        assert_eq!(
+            unquote_git_path(r#""dir/\9""#).err().unwrap(),
+            GitPathError::InvalidEscape(5)
+        );
+        assert_eq!(
+            unquote_git_path(r#""dir/\30""#).err().unwrap(),
+            GitPathError::InvalidEscape(5)
+        );
+        assert!(matches!(
+            unquote_git_path(r#""dir/\303""#).err().unwrap(),
+            GitPathError::InvalidPath(FilePathError::InvalidUTF8(_))
+        ));
+    }
+
+    #[test]
+    fn diff_git_header() {
+        let paths = |old: &str, new: &str| DiffPaths {
+            old: FilePathBuf::new(old).unwrap(),
+            new: FilePathBuf::new(new).unwrap(),
+        };
+
+        assert_eq!(
+            parse_diff_git_header("diff --git a/foo/bar.txt b/foo/bar.txt").unwrap(),
+            paths("foo/bar.txt", "foo/bar.txt")
+        );
+        assert_eq!(
+            parse_diff_git_header("diff --git a/foo/bar.txt b/foo/baz.txt").unwrap(),
+            paths("foo/bar.txt", "foo/baz.txt")
+        );
+        assert_eq!(
+            parse_diff_git_header("diff --git a/x b/y.txt b/x b/y.txt").unwrap(),
+            paths("x b/y.txt", "x b/y.txt")
+        );
+        assert_eq!(
+            parse_diff_git_header(r#"diff --git "a/caf\303\251 b/x" "b/caf\303\251 b/y""#).unwrap(),
+            paths("café b/x", "café b/y")
+        );
+        assert_eq!(
+            parse_diff_git_header(r#"diff --git a/cafe.txt "b/caf\303\251.txt""#).unwrap(),
+            paths("cafe.txt", "café.txt")
+        );
+
+        // Ambiguous.
+        assert_eq!(
+            parse_diff_git_header("diff --git a/x b/y b/z")
+                .err()
+                .unwrap(),
+            GitPathError::InvalidHeader
+        );
+        assert_eq!(
+            parse_diff_git_header("diff --git a/x c/y").err().unwrap(),
+            GitPathError::InvalidHeader
+        );
+        assert_eq!(
+            parse_diff_git_header("diff --git a/x/../y b/y")
+                .err()
+                .unwrap(),
+            GitPathError::InvalidPath(FilePathError::ParentDirectory(ErrorPath::from("x")))
+        );
+    }
+
+    #[test]
+    fn diff_file_header() {
+        let path = |path| Some(FilePathBuf::new(path).unwrap());
+
+        assert_eq!(
+            parse_diff_file_header("--- a/foo/bar.txt").unwrap(),
+            DiffFileHeader::Old(path("foo/bar.txt"))
+        );
+        assert_eq!(
+            parse_diff_file_header("+++ b/foo/bar baz.txt\t").unwrap(),
+            DiffFileHeader::New(path("foo/bar baz.txt"))
+        );
+        assert_eq!(
+            parse_diff_file_header("+++ /dev/null").unwrap(),
+            DiffFileHeader::New(None)
+        );
+        assert_eq!(
+            parse_diff_file_header(r#"--- "a/caf\303\251.txt""#).unwrap(),
+            DiffFileHeader::Old(path("café.txt"))
+        );
+        assert_eq!(
+            parse_diff_file_header("--- b/foo.txt").err().unwrap(),
+            GitPathError::InvalidHeader
+        );
+        assert_eq!(
+            parse_diff_file_header("diff --git a/foo.txt b/foo.txt")
+                .err()
+                .unwrap(),
+            GitPathError::InvalidHeader
+        );
+    }
+}
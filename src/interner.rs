@@ -0,0 +1,412 @@
+use {
+    crate::*,
+    ministr::NonEmptyStr,
+    std::{
+        collections::HashMap,
+        sync::{Arc, PoisonError, RwLock, RwLockReadGuard},
+    },
+};
+
+/// A compact ID of a [`FilePath`] interned in a [`FilePathInterner`].
+///
+/// Only valid for the [`FilePathInterner`] which returned it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct FilePathId(u32);
+
+impl FilePathId {
+    /// Returns the raw `u32` value of the [`FilePathId`], e.g. for serialization.
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Creates a [`FilePathId`] from the raw `u32` value previously returned by [`FilePathId::as_u32`].
+    ///
+    /// NOTE: [`FilePathInterner`] methods panic if passed an ID which was not returned by this [`FilePathInterner`].
+    pub fn from_u32(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+/// Used as the parent ID of root path components.
+const NO_PARENT: u32 = u32::MAX;
+
+#[derive(Clone, Copy)]
+struct Node {
+    /// ID of the parent directory path, or `NO_PARENT`.
+    parent: u32,
+    /// Index of the leaf path component in `FilePathInterner::components`.
+    component: u32,
+}
+
+/// Deduplicates [`FilePath`]'s, storing each unique path component and each unique directory prefix only once.
+///
+/// Each interned [`FilePath`] is represented as a [`FilePathId`], which refers to a pair of
+/// its parent directory [`FilePathId`] (if any) and its file name component.
+/// Interning a [`FilePath`] also interns all of its parent directory paths.
+///
+/// Only ever grows; interned [`FilePath`]'s are never removed.
+///
+/// Also see [`SyncFilePathInterner`] for a thread-safe version.
+///
+/// # Panics
+///
+/// Methods which take a [`FilePathId`] panic if the ID was not returned by this [`FilePathInterner`].
+#[derive(Clone, Default)]
+pub struct FilePathInterner {
+    components: Vec<Arc<str>>,
+    component_ids: HashMap<Arc<str>, u32>,
+    nodes: Vec<Node>,
+    node_ids: HashMap<(u32, u32), u32>,
+}
+
+impl FilePathInterner {
+    /// Creates an empty [`FilePathInterner`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of interned unique file paths, including all parent directory paths.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the number of interned unique path components.
+    pub fn num_components(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Interns the `path` and all of its parent directory paths, if necessary, and returns its [`FilePathId`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of interned file paths or path components overflows a `u32`.
+    pub fn intern<P: AsRef<FilePath> + ?Sized>(&mut self, path: &P) -> FilePathId {
+        let mut parent = NO_PARENT;
+
+        for component in path.as_ref().components() {
+            let component = self.intern_component(component);
+
+            let nodes = &mut self.nodes;
+            parent = *self.node_ids.entry((parent, component)).or_insert_with(|| {
+                let id = new_id(nodes.len());
+                nodes.push(Node { parent, component });
+                id
+            });
+        }
+
+        debug_assert!(parent != NO_PARENT, "empty `FilePath`'s are invalid");
+        FilePathId(parent)
+    }
+
+    /// Returns the [`FilePathId`] of the `path` if it was previously interned, directly or as a parent directory path.
+    pub fn get<P: AsRef<FilePath> + ?Sized>(&self, path: &P) -> Option<FilePathId> {
+        let mut parent = NO_PARENT;
+
+        for component in path.as_ref().components() {
+            let component = *self.component_ids.get(component.as_str())?;
+            parent = *self.node_ids.get(&(parent, component))?;
+        }
+
+        Some(FilePathId(parent))
+    }
+
+    /// Returns the [`FilePathId`] of the parent directory path of the file path with [`id`](FilePathId), if any.
+    ///
+    /// E.g. `"foo/bar/baz.txt"` -> `"foo/bar"`, `"foo"` -> `None`.
+    pub fn parent(&self, id: FilePathId) -> Option<FilePathId> {
+        let parent = self.node(id).parent;
+        (parent != NO_PARENT).then_some(FilePathId(parent))
+    }
+
+    /// Returns the file name portion (i.e. the last/leaf component) of the file path with [`id`](FilePathId).
+    pub fn file_name(&self, id: FilePathId) -> &FileName {
+        self.component(self.node(id).component)
+    }
+
+    /// Returns the [`FilePathBuf`] with [`id`](FilePathId).
+    pub fn resolve(&self, id: FilePathId) -> FilePathBuf {
+        let mut builder = FilePathBuilder::with_capacity(self.path_len(id));
+        // Must succeed - interned paths are valid and the builder is empty.
+        unsafe {
+            self.resolve_into(id, &mut builder)
+                .unwrap_unchecked_dbg_msg("interned `FilePath`'s must be valid");
+            builder
+                .build()
+                .unwrap_unchecked_dbg_msg("interned `FilePath`'s must be non-empty")
+        }
+    }
+
+    /// Appends the file path with [`id`](FilePathId) to the [`builder`](FilePathBuilder).
+    ///
+    /// Returns an [`error`](FilePathError) if the resulting path would be too long; the `builder` is unchanged in this case.
+    pub fn resolve_into(
+        &self,
+        id: FilePathId,
+        builder: &mut FilePathBuilder,
+    ) -> Result<(), FilePathError> {
+        let path_len = self.path_len(id);
+        let string = builder.as_mut_string();
+
+        let start = if string.is_empty() {
+            0
+        } else {
+            string.len() + 1
+        };
+        let end = start + path_len;
+
+        if end > MAX_PATH_LEN {
+            return Err(FilePathError::PathTooLong(end));
+        }
+
+        // Path components are written leaf to root, so reserve the space first.
+        // Zeros are valid UTF-8 and are all overwritten below.
+        let bytes = unsafe { string.as_mut_vec() };
+        if !bytes.is_empty() {
+            bytes.push(SEPARATOR_BYTE);
+        }
+        bytes.resize(end, 0);
+
+        let mut end = end;
+        let mut node = self.node(id);
+
+        loop {
+            let component = self.component(node.component).as_bytes();
+            bytes[end - component.len()..end].copy_from_slice(component);
+            end -= component.len();
+
+            if node.parent == NO_PARENT {
+                break;
+            }
+
+            end -= 1;
+            bytes[end] = SEPARATOR_BYTE;
+
+            node = self.nodes[node.parent as usize];
+        }
+
+        debug_assert_eq!(end, start);
+
+        Ok(())
+    }
+
+    fn intern_component(&mut self, component: &FileName) -> u32 {
+        if let Some(id) = self.component_ids.get(component.as_str()) {
+            return *id;
+        }
+
+        let id = new_id(self.components.len());
+        let component: Arc<str> = Arc::from(component.as_str());
+        self.components.push(component.clone());
+        self.component_ids.insert(component, id);
+        id
+    }
+
+    fn node(&self, id: FilePathId) -> Node {
+        *self.nodes.get(id.0 as usize).expect("invalid `FilePathId`")
+    }
+
+    fn component(&self, component: u32) -> &FileName {
+        // Only ever contains (non-empty) valid path components.
+        unsafe {
+            FileName::from_ne_str(NonEmptyStr::new_unchecked(
+                &self.components[component as usize],
+            ))
+        }
+    }
+
+    /// Returns the length in bytes of the file path with `id`.
+    fn path_len(&self, id: FilePathId) -> usize {
+        let mut node = self.node(id);
+        let mut path_len = self.components[node.component as usize].len();
+
+        while node.parent != NO_PARENT {
+            node = self.nodes[node.parent as usize];
+            // Count the separator.
+            path_len += self.components[node.component as usize].len() + 1;
+        }
+
+        path_len
+    }
+}
+
+fn new_id(len: usize) -> u32 {
+    u32::try_from(len)
+        .ok()
+        .filter(|id| *id != NO_PARENT)
+        .expect("too many interned file paths / path components")
+}
+
+/// Thread-safe version of a [`FilePathInterner`].
+///
+/// Interning an already interned [`FilePath`] only takes a read lock.
+#[derive(Default)]
+pub struct SyncFilePathInterner(RwLock<FilePathInterner>);
+
+impl SyncFilePathInterner {
+    /// Creates an empty [`SyncFilePathInterner`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// See [`FilePathInterner::intern`].
+    pub fn intern<P: AsRef<FilePath> + ?Sized>(&self, path: &P) -> FilePathId {
+        if let Some(id) = self.read().get(path) {
+            return id;
+        }
+
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .intern(path)
+    }
+
+    /// See [`FilePathInterner::get`].
+    pub fn get<P: AsRef<FilePath> + ?Sized>(&self, path: &P) -> Option<FilePathId> {
+        self.read().get(path)
+    }
+
+    /// See [`FilePathInterner::parent`].
+    pub fn parent(&self, id: FilePathId) -> Option<FilePathId> {
+        self.read().parent(id)
+    }
+
+    /// See [`FilePathInterner::resolve`].
+    pub fn resolve(&self, id: FilePathId) -> FilePathBuf {
+        self.read().resolve(id)
+    }
+
+    /// See [`FilePathInterner::resolve_into`].
+    pub fn resolve_into(
+        &self,
+        id: FilePathId,
+        builder: &mut FilePathBuilder,
+    ) -> Result<(), FilePathError> {
+        self.read().resolve_into(id, builder)
+    }
+
+    /// Locks the [`FilePathInterner`] for reading, e.g. to access the borrowed [`file names`](FilePathInterner::file_name)
+    /// or to perform multiple queries under a single lock.
+    pub fn read(&self) -> RwLockReadGuard<'_, FilePathInterner> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn into_inner(self) -> FilePathInterner {
+        self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<FilePathInterner> for SyncFilePathInterner {
+    fn from(interner: FilePathInterner) -> Self {
+        Self(RwLock::new(interner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> &FilePath {
+        FilePath::new(path).unwrap()
+    }
+
+    #[test]
+    fn interner() {
+        let mut interner = FilePathInterner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern(path("textures/props/barrel.png"));
+        // "textures", "textures/props", "textures/props/barrel.png"
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.num_components(), 3);
+
+        let b = interner.intern(path("textures/props/crate.png"));
+        assert_eq!(interner.len(), 4);
+        assert_eq!(interner.num_components(), 4);
+
+        // Same component, different parents.
+        let c = interner.intern(path("textures/textures"));
+        assert_eq!(interner.len(), 5);
+        assert_eq!(interner.num_components(), 4);
+
+        assert_eq!(interner.intern(path("textures//props/./barrel.png")), a);
+        assert_eq!(interner.len(), 5);
+
+        assert_ne!(a, b);
+        assert_eq!(interner.parent(a), interner.parent(b));
+        assert_eq!(interner.parent(a), interner.get(path("textures/props")));
+        assert_eq!(interner.get(path("textures/props/barrel.png")), Some(a));
+        assert_eq!(interner.get(path("props")), None);
+        assert_eq!(interner.get(path("textures/props/bill.png")), None);
+
+        let textures = interner.get(path("textures")).unwrap();
+        assert_eq!(interner.parent(textures), None);
+        assert_eq!(interner.parent(c), Some(textures));
+
+        assert_eq!(interner.file_name(a).as_str(), "barrel.png");
+        assert_eq!(interner.file_name(c).as_str(), "textures");
+
+        assert_eq!(interner.resolve(a).as_str(), "textures/props/barrel.png");
+        assert_eq!(interner.resolve(b).as_str(), "textures/props/crate.png");
+        assert_eq!(interner.resolve(c).as_str(), "textures/textures");
+        assert_eq!(interner.resolve(textures).as_str(), "textures");
+
+        let mut builder = FilePathBuf::new("data").unwrap().into_builder();
+        interner.resolve_into(a, &mut builder).unwrap();
+        assert_eq!(
+            builder.build().unwrap().as_str(),
+            "data/textures/props/barrel.png"
+        );
+
+        let id = FilePathId::from_u32(a.as_u32());
+        assert_eq!(id, a);
+    }
+
+    #[test]
+    fn resolve_into_too_long() {
+        let mut interner = FilePathInterner::new();
+        let id = interner.intern(path("foo/bar"));
+
+        let long_path = "a".repeat(MAX_COMPONENT_LEN);
+        let mut builder = FilePathBuilder::new();
+        while builder.len() + MAX_COMPONENT_LEN < MAX_PATH_LEN {
+            builder.push(&long_path).unwrap();
+        }
+        let len = builder.len();
+
+        assert_eq!(
+            interner.resolve_into(id, &mut builder).err().unwrap(),
+            FilePathError::PathTooLong(len + 1 + "foo/bar".len())
+        );
+        assert_eq!(builder.len(), len);
+    }
+
+    #[test]
+    fn sync_interner() {
+        let interner = Arc::new(SyncFilePathInterner::new());
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = interner.clone();
+                std::thread::spawn(move || {
+                    (0..16)
+                        .map(|idx| {
+                            interner.intern(&FilePathBuf::new(format!("foo/{}.txt", idx)).unwrap())
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let ids: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert!(ids.iter().all(|ids_| ids_ == &ids[0]));
+
+        assert_eq!(interner.read().len(), 17);
+        assert_eq!(interner.resolve(ids[0][7]).as_str(), "foo/7.txt");
+        assert_eq!(interner.read().file_name(ids[0][7]).as_str(), "7.txt");
+        assert_eq!(interner.parent(ids[0][7]), interner.get(path("foo")));
+    }
+}
@@ -1,13 +1,15 @@
 use {
     crate::*,
+    core::iter::{DoubleEndedIterator, FusedIterator, Iterator},
     ministr::NonEmptyStr,
-    miniunchecked::*,
-    std::{
-        iter::{DoubleEndedIterator, FusedIterator, Iterator},
-        path::{Component, Components, Path},
-    },
 };
 
+#[cfg(feature = "std")]
+use std::path::{Component, Components, Path};
+
+#[cfg(not(feature = "std"))]
+use core::{iter::Filter, str::Split};
+
 /// Lightweight double-ended iterator over the canonical [`path string`](FilePathBuf) using string splitting.
 pub struct FilePathBufIter<'a>(Option<&'a FilePath>);
 
@@ -19,7 +21,7 @@ impl<'a> FilePathBufIter<'a> {
 }
 
 impl<'a> Iterator for FilePathBufIter<'a> {
-    type Item = FilePathComponent<'a>;
+    type Item = &'a FileName;
 
     fn next(&mut self) -> Option<Self::Item> {
         next_impl(&mut self.0, pop_path_component_front)
@@ -36,8 +38,8 @@ impl<'a> FusedIterator for FilePathBufIter<'a> {}
 
 fn next_impl<'a>(
     src_path: &mut Option<&'a FilePath>,
-    pop: fn(&FilePath) -> (FilePathComponent, Option<&FilePath>),
-) -> Option<FilePathComponent<'a>> {
+    pop: fn(&FilePath) -> (&FileName, Option<&FilePath>),
+) -> Option<&'a FileName> {
     src_path.map(|path| {
         let (comp, path) = pop(path);
         *src_path = path;
@@ -46,30 +48,26 @@ fn next_impl<'a>(
 }
 
 /// The caller guarantees `path` is a canonical `FilePath`.
-pub(crate) fn pop_path_component_front(
-    path: &FilePath,
-) -> (FilePathComponent<'_>, Option<&FilePath>) {
+pub(crate) fn pop_path_component_front(path: &FilePath) -> (&FileName, Option<&FilePath>) {
     if let Some((comp, path)) = path.as_str().split_once(SEPARATOR_CHAR) {
         (
-            unsafe { NonEmptyStr::new_unchecked(comp) },
+            unsafe { FileName::from_ne_str(NonEmptyStr::new_unchecked(comp)) },
             NonEmptyStr::new(path).map(|path| unsafe { FilePath::from_str(path) }),
         )
     } else {
-        (&path.0, None)
+        (unsafe { FileName::from_ne_str(&path.0) }, None)
     }
 }
 
 /// The caller guarantees `path` is a canonical `FilePath`.
-pub(crate) fn pop_path_component_back(
-    path: &FilePath,
-) -> (FilePathComponent<'_>, Option<&FilePath>) {
+pub(crate) fn pop_path_component_back(path: &FilePath) -> (&FileName, Option<&FilePath>) {
     if let Some((path, comp)) = path.as_str().rsplit_once(SEPARATOR_CHAR) {
         (
-            unsafe { NonEmptyStr::new_unchecked(comp) },
+            unsafe { FileName::from_ne_str(NonEmptyStr::new_unchecked(comp)) },
             NonEmptyStr::new(path).map(|path| unsafe { FilePath::from_str(path) }),
         )
     } else {
-        (&path.0, None)
+        (unsafe { FileName::from_ne_str(&path.0) }, None)
     }
 }
 
@@ -79,16 +77,18 @@ pub(crate) fn pop_path_component_back(
 /// 1) contain `CurDir` components (`.`),
 /// 2) contain repeated path component separators,
 /// 3) use different path component separators depending on the OS.
-pub struct FilePathIter<'a>(pub(crate) Components<'a>);
+///
+/// Without the `std` feature, splits the path string on forward slashes instead.
+pub struct FilePathIter<'a>(pub(crate) RawComponents<'a>);
 
 impl<'a> FilePathIter<'a> {
     pub(crate) fn new(src: &'a FilePath) -> Self {
-        Self(Path::new(src.as_str()).components())
+        Self(raw_components(src.as_str()))
     }
 }
 
 impl<'a> Iterator for FilePathIter<'a> {
-    type Item = FilePathComponent<'a>;
+    type Item = &'a FileName;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(get_component)
@@ -103,12 +103,34 @@ impl<'a> DoubleEndedIterator for FilePathIter<'a> {
 
 impl<'a> FusedIterator for FilePathIter<'a> {}
 
-fn get_component<'a>(component: Component<'a>) -> FilePathComponent<'a> {
+#[cfg(feature = "std")]
+pub(crate) type RawComponents<'a> = Components<'a>;
+
+/// String-based iterator over the (potentially non-canonical) path string's components,
+/// skipping empty (i.e. repeated separators) and `CurDir` (`.`) components.
+#[cfg(not(feature = "std"))]
+pub(crate) type RawComponents<'a> = Filter<Split<'a, char>, fn(&&'a str) -> bool>;
+
+#[cfg(feature = "std")]
+pub(crate) fn raw_components(path: &str) -> RawComponents<'_> {
+    Path::new(path).components()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn raw_components(path: &str) -> RawComponents<'_> {
+    path.split(SEPARATOR_CHAR)
+        .filter(|component| !component.is_empty() && *component != ".")
+}
+
+#[cfg(feature = "std")]
+fn get_component<'a>(component: Component<'a>) -> &'a FileName {
     match component {
         // Must succeed - `FilePath`'s only contain valid (non-empty) path components
         Component::Normal(component) => unsafe {
-            NonEmptyStr::new_unchecked(component.to_str().unwrap_unchecked_dbg_msg(
-                "`FilePath`'s must only contain valid (UTF-8) path components",
+            FileName::from_ne_str(NonEmptyStr::new_unchecked(
+                component.to_str().unwrap_unchecked_dbg_msg(
+                    "`FilePath`'s must only contain valid (UTF-8) path components",
+                ),
             ))
         },
         // Must succeed - `FilePath`'s only contain valid (normal) path components.
@@ -117,3 +139,13 @@ fn get_component<'a>(component: Component<'a>) -> FilePathComponent<'a> {
         },
     }
 }
+
+#[cfg(not(feature = "std"))]
+fn get_component(component: &str) -> &FileName {
+    debug_assert!(
+        component != "..",
+        "`FilePath`'s must only contain valid (normal) path components"
+    );
+    // Must succeed - `raw_components()` skips empty path components.
+    unsafe { FileName::from_ne_str(NonEmptyStr::new_unchecked(component)) }
+}
@@ -1,24 +1,103 @@
 //! # minifilepath
 //!
 //! Some simple Rust wrapper types for non-empty, relative, case-sensitive, UTF-8 file paths.
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default) - [`std::path`] conversions, [`Path`](std::path::Path)-based parsing
+//!   and [`PathBuf`](std::path::PathBuf) [`error`](FilePathError) payloads, the [`FilePathInterner`],
+//!   the [`PackReader`] / [`PackWriter`] pack files, the [`read_path_list`] / [`PathListWriter`] path lists,
+//!   and [`file_url`]'s.
+//! - `serde` - `Serialize` / `Deserialize` implementations for [`FilePath`]'s and [`FilePathBuf`]'s,
+//!   and the [`canonicalize`] `with`-module. Deserialization errors only carry the message of the [`FilePathError`],
+//!   as the deserializer's custom error; `FilePathBuf::try_deserialize` returns the typed error.
+//!
+//! Without the `std` feature the crate only depends on `core` and `alloc`,
+//! and parses file paths from strings, using forward slashes as path component separators.
+
+#![no_std]
 
+extern crate alloc;
+// Tests use `std` collections even without the `std` feature.
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+mod arena;
+mod arraybuilder;
+mod asfilepath;
+mod asseturi;
 mod builder;
+mod depfile;
+mod dirpath;
+mod encoding;
 mod error;
+mod filename;
+mod git;
+#[cfg(feature = "std")]
+mod interner;
 mod iter;
+mod linked;
+mod manifest;
+mod map;
+mod ord;
+#[cfg(feature = "std")]
+mod pack;
 mod path;
 mod pathbuf;
+#[cfg(feature = "std")]
+mod pathlist;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod smallpathbuf;
+mod table;
+mod typed;
+mod url;
 mod util;
 
 pub(crate) use util::*;
 pub use {
+    arena::*,
+    arraybuilder::*,
+    asfilepath::*,
+    asseturi::*,
     builder::*,
+    depfile::*,
+    dirpath::*,
+    encoding::*,
     error::*,
+    filename::*,
+    git::*,
     iter::{FilePathBufIter, FilePathIter},
+    linked::*,
+    manifest::*,
+    map::*,
+    ord::*,
     path::*,
     pathbuf::*,
+    smallpathbuf::*,
+    table::*,
+    typed::*,
+    url::*,
 };
 
-pub type FilePathComponent<'a> = &'a ministr::NonEmptyStr;
+#[cfg(feature = "serde")]
+pub use serde_impls::canonicalize;
+#[cfg(feature = "std")]
+pub use {interner::*, pack::*, pathlist::*};
+
+/// The (possibly invalid) source path type [`FilePath`]'s are parsed from:
+/// [`std::path::Path`] with the `std` feature, or a `str` otherwise.
+#[cfg(feature = "std")]
+pub type RawPath = std::path::Path;
+
+/// The (possibly invalid) source path type [`FilePath`]'s are parsed from:
+/// [`std::path::Path`] with the `std` feature, or a `str` otherwise.
+#[cfg(not(feature = "std"))]
+pub type RawPath = str;
+
+/// A (validated) file path component.
+#[deprecated(note = "use `&FileName` instead")]
+pub type FilePathComponent<'a> = &'a FileName;
 
 pub const SEPARATOR_CHAR: char = '/';
 pub const SEPARATOR_BYTE: u8 = b'/';
@@ -33,11 +112,21 @@ pub const MAX_PATH_LEN: usize = u16::MAX as usize;
 pub const MAX_NUM_COMPONENTS: usize = MAX_PATH_LEN / 2; // `MAX_PATH_LEN == 8` -> "a/a/a/ab", `MAX_NUM_COMPONENTS == 4 == MAX_PATH_LEN / 2`
 
 use {
+    alloc::{
+        borrow::ToOwned,
+        boxed::Box,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    },
+    core::str,
     ministr::NonEmptyStr,
     miniunchecked::*,
-    std::{path::PathBuf, str},
 };
 
+#[cfg(test)]
+use alloc::format;
+
 /// Attempts to validate the file path `component`.
 ///
 /// Disallows
@@ -46,11 +135,11 @@ use {
 /// - components which contain invalid characters (`'\'`, `'/'`, `':'`, `'*'`, `'?'`, `'"'`, `'<'`, `'>'`, `'|'`) or ASCII control characters,
 /// - components which are reserved file names (case-insensitive) or reserved file names with an extension
 ///   (`"AUX"`, `"COM?"`, `"CON"`, `"LPT?"`, `"NUL"`, `"PRN"`, where `?` is one of ASCII digits [`1` .. `9`]).
-pub fn is_valid_path_component(component: FilePathComponent<'_>) -> bool {
-    if component == "." || component == ".." {
-        false
+pub fn is_valid_path_component(component: &str) -> bool {
+    if let Some(component) = NonEmptyStr::new(component) {
+        validate_normal_path_component(component, ErrorPath::new).is_ok()
     } else {
-        validate_path_component(component, PathBuf::new).is_ok()
+        false
     }
 }
 
@@ -62,8 +151,8 @@ pub fn is_valid_path_component(component: FilePathComponent<'_>) -> bool {
 /// - ".gitignore" -> { file_stem: None, extension: "gitgnore" } (NOTE: this is different from standard library behaviour)
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct FileStemAndExtension<'a> {
-    pub file_stem: Option<FilePathComponent<'a>>,
-    pub extension: FilePathComponent<'a>,
+    pub file_stem: Option<&'a NonEmptyStr>,
+    pub extension: &'a NonEmptyStr,
 }
 
 /// Splits the `file_name` into the file stem and extension parts.
@@ -81,13 +170,11 @@ pub struct FileStemAndExtension<'a> {
 /// - `"foo.bar.txt"` -> `Some((Some("foo.bar"), "txt"))`
 /// - `"foo."` -> invalid path (cannot end with a period), but this returns `None`
 /// - `"foo"` -> `None`
-pub fn file_stem_and_extension(
-    file_name: FilePathComponent<'_>,
-) -> Option<FileStemAndExtension<'_>> {
+pub fn file_stem_and_extension(file_name: &FileName) -> Option<FileStemAndExtension<'_>> {
     let mut iter = file_name.as_str().as_bytes().rsplitn(2, |b| *b == b'.');
     let extension = unsafe {
         iter.next()
-            .unwrap_unchecked_dbg_msg("`FilePathComponent`'s must be non-empty")
+            .unwrap_unchecked_dbg_msg("`FileName`'s must be non-empty")
     };
 
     if let Some(file_name) = iter.next() {
@@ -115,7 +202,7 @@ pub struct FilePathAndName<'a> {
     pub file_path: &'a FilePath,
     /// The file name part of the [`FilePath`].
     /// E.g. `"foo/bar"` -> `"bar"`, `"foo/bar/baz.txt"` -> `"baz.txt"`.
-    pub file_name: FilePathComponent<'a>,
+    pub file_name: &'a FileName,
 }
 
 /// Splits the `file_path` into the file path and file name parts.
@@ -136,7 +223,7 @@ pub fn file_path_and_name(file_path: &FilePathBuf) -> Option<FilePathAndName<'_>
 
     iter.next().map(|file_path| FilePathAndName {
         file_path: unsafe { FilePath::new_unchecked(str::from_utf8_unchecked(file_path)) },
-        file_name: unsafe { NonEmptyStr::new_unchecked(str::from_utf8_unchecked(file_name)) },
+        file_name: unsafe { FileName::new_unchecked(str::from_utf8_unchecked(file_name)) },
     })
 }
 
@@ -147,28 +234,31 @@ mod tests {
     #[test]
     fn file_name_and_extension_test() {
         assert_eq!(
-            file_stem_and_extension(nestr!(".txt")),
+            file_stem_and_extension(FileName::new(".txt").unwrap()),
             Some(FileStemAndExtension {
                 file_stem: None,
                 extension: nestr!("txt")
             })
         );
         assert_eq!(
-            file_stem_and_extension(nestr!("foo.txt")),
+            file_stem_and_extension(FileName::new("foo.txt").unwrap()),
             Some(FileStemAndExtension {
                 file_stem: Some(nestr!("foo")),
                 extension: nestr!("txt")
             })
         );
         assert_eq!(
-            file_stem_and_extension(nestr!("foo.bar.txt")),
+            file_stem_and_extension(FileName::new("foo.bar.txt").unwrap()),
             Some(FileStemAndExtension {
                 file_stem: Some(nestr!("foo.bar")),
                 extension: nestr!("txt")
             })
         );
-        assert_eq!(file_stem_and_extension(nestr!("foo")), None,);
-        assert_eq!(file_stem_and_extension(nestr!("foo.")), None,);
+        assert_eq!(file_stem_and_extension(FileName::new("foo").unwrap()), None,);
+        assert_eq!(
+            file_stem_and_extension(unsafe { FileName::from_ne_str(nestr!("foo.")) }),
+            None,
+        );
     }
 
     #[test]
@@ -192,35 +282,35 @@ mod tests {
             file_path_and_name(&FilePathBuf::new("foo/bar").unwrap()),
             Some(FilePathAndName {
                 file_path: FilePath::new("foo").unwrap(),
-                file_name: nestr!("bar")
+                file_name: FileName::new("bar").unwrap()
             })
         );
         assert_eq!(
             file_path_and_name(&FilePathBuf::new("foo bar/baz bob").unwrap()),
             Some(FilePathAndName {
                 file_path: FilePath::new("foo bar").unwrap(),
-                file_name: nestr!("baz bob")
+                file_name: FileName::new("baz bob").unwrap()
             })
         );
         assert_eq!(
             file_path_and_name(&FilePathBuf::new("foo/bar/baz bob").unwrap()),
             Some(FilePathAndName {
                 file_path: FilePath::new("foo/bar").unwrap(),
-                file_name: nestr!("baz bob")
+                file_name: FileName::new("baz bob").unwrap()
             })
         );
         assert_eq!(
             file_path_and_name(&FilePathBuf::new("foo/bar.txt").unwrap()),
             Some(FilePathAndName {
                 file_path: FilePath::new("foo").unwrap(),
-                file_name: nestr!("bar.txt")
+                file_name: FileName::new("bar.txt").unwrap()
             })
         );
         assert_eq!(
             file_path_and_name(&FilePathBuf::new("foo/.txt").unwrap()),
             Some(FilePathAndName {
                 file_path: FilePath::new("foo").unwrap(),
-                file_name: nestr!(".txt")
+                file_name: FileName::new(".txt").unwrap()
             })
         );
     }
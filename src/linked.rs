@@ -0,0 +1,337 @@
+use {
+    crate::*,
+    alloc::sync::Arc,
+    core::{
+        convert::Infallible,
+        fmt::{Display, Formatter},
+        hash::{Hash, Hasher},
+        iter::FusedIterator,
+    },
+    ministr::{NonEmptyStr, NonEmptyString},
+};
+
+/// Persistent (immutable, reference-counted) file path, represented as a link to its parent directory path
+/// and its own file name (i.e. the last/leaf component).
+///
+/// Creating a child path via [`join`](Self::join) is `O(1)`, never copies the parent path
+/// and shares the parent path between all of its children; cloning is `O(1)`.
+/// Useful when creating large numbers of child paths, e.g. in recursive directory processing.
+///
+/// [`MAX_COMPONENT_LEN`] and [`MAX_PATH_LEN`] limits are enforced incrementally, as the child paths are created.
+///
+/// Hashed and compared for equality componentwise, consistent with [`FilePath`].
+/// Formatted in canonical string representation, consistent with [`FilePathBuf`].
+#[derive(Clone)]
+pub struct LinkedFilePath(Arc<LinkedFilePathNode>);
+
+struct LinkedFilePathNode {
+    parent: Option<LinkedFilePath>,
+    file_name: Box<str>,
+    /// Length in bytes of the (canonical) file path, including the parent path and the separator.
+    len: usize,
+    /// Number of components in the file path.
+    depth: usize,
+}
+
+impl LinkedFilePath {
+    /// Creates a single-component [`LinkedFilePath`] from the `file_name`.
+    pub fn new(file_name: &FileName) -> Self {
+        Self::new_impl(None, file_name)
+    }
+
+    /// Creates a [`LinkedFilePath`] from the `path`, creating all of its parent directory paths.
+    pub fn from_file_path(path: &FilePath) -> Self {
+        let mut components = path.components();
+
+        let file_name = unsafe {
+            components
+                .next()
+                .unwrap_unchecked_dbg_msg("empty `FilePath`'s are invalid")
+        };
+        let root = Self::new_impl(None, file_name);
+
+        // `FilePath`'s contain valid components and are not too long.
+        components.fold(root, |parent, component| {
+            Self::new_impl(Some(parent), component)
+        })
+    }
+
+    /// Tries to create a child path of this path with the `file_name` as its last/leaf component.
+    ///
+    /// Returns an [`error`](FilePathError) if the resulting path would be too long.
+    pub fn join(&self, file_name: &FileName) -> Result<Self, FilePathError> {
+        // Count the separator.
+        let len = self.len() + 1 + file_name.len();
+        if len > MAX_PATH_LEN {
+            return Err(FilePathError::PathTooLong(len));
+        }
+
+        Ok(Self::new_impl(Some(self.clone()), file_name))
+    }
+
+    /// Returns the length in bytes of the (canonical) [`LinkedFilePath`]. Always > 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    /// Returns the number of components in the [`LinkedFilePath`]. Always > 0.
+    pub fn depth(&self) -> usize {
+        self.0.depth
+    }
+
+    /// Returns the parent directory path of the [`LinkedFilePath`], if any.
+    pub fn parent(&self) -> Option<&LinkedFilePath> {
+        self.0.parent.as_ref()
+    }
+
+    /// Returns the file name portion of the [`LinkedFilePath`] (i.e. the last/leaf component).
+    pub fn file_name(&self) -> &FileName {
+        // Only contains (non-empty) valid path components.
+        unsafe { FileName::from_ne_str(NonEmptyStr::new_unchecked(&self.0.file_name)) }
+    }
+
+    /// Returns an [`iterator`](LinkedFilePathIter) over the [`file name`](FileName) components of the [`LinkedFilePath`],
+    /// leaf to root.
+    pub fn components_rev(&self) -> LinkedFilePathIter<'_> {
+        LinkedFilePathIter(Some(self))
+    }
+
+    /// Returns `true` if both [`LinkedFilePath`]'s share the same node (and thus are equal).
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Creates the (canonical) [`FilePathBuf`] from the [`LinkedFilePath`].
+    pub fn to_file_path_buf(&self) -> FilePathBuf {
+        let len = self.len();
+        let mut bytes = vec![0; len];
+        let mut end = len;
+
+        for (idx, component) in self.components_rev().enumerate() {
+            if idx > 0 {
+                end -= 1;
+                bytes[end] = SEPARATOR_BYTE;
+            }
+            bytes[end - component.len()..end].copy_from_slice(component.as_bytes());
+            end -= component.len();
+        }
+
+        debug_assert_eq!(end, 0);
+
+        // Only contains valid components and separators.
+        FilePathBuf(unsafe { NonEmptyString::new_unchecked(String::from_utf8_unchecked(bytes)) })
+    }
+
+    fn new_impl(parent: Option<Self>, file_name: &FileName) -> Self {
+        let (len, depth) = parent.as_ref().map_or((file_name.len(), 1), |parent| {
+            // Count the separator.
+            (parent.len() + 1 + file_name.len(), parent.depth() + 1)
+        });
+        debug_assert!(len <= MAX_PATH_LEN);
+
+        Self(Arc::new(LinkedFilePathNode {
+            parent,
+            file_name: file_name.as_str().into(),
+            len,
+            depth,
+        }))
+    }
+
+    /// Calls `f` for each component, root to leaf, until it returns an error.
+    ///
+    /// Does not allocate. Buffers the components on the stack in chunks
+    /// and only recurses once per chunk, to not overflow the stack for deep paths.
+    fn try_for_each_component<E, F: FnMut(&FileName) -> Result<(), E>>(
+        &self,
+        f: &mut F,
+    ) -> Result<(), E> {
+        const CHUNK_LEN: usize = 64;
+
+        let mut chunk = [self.file_name(); CHUNK_LEN];
+        let mut len = 0;
+        let mut node = Some(self);
+
+        while let Some(path) = node.filter(|_| len < CHUNK_LEN) {
+            chunk[len] = path.file_name();
+            len += 1;
+            node = path.parent();
+        }
+
+        if let Some(parent) = node {
+            parent.try_for_each_component(f)?;
+        }
+
+        chunk[..len]
+            .iter()
+            .rev()
+            .try_for_each(|component| f(component))
+    }
+}
+
+impl From<&FilePath> for LinkedFilePath {
+    fn from(path: &FilePath) -> Self {
+        Self::from_file_path(path)
+    }
+}
+
+impl From<&LinkedFilePath> for FilePathBuf {
+    fn from(path: &LinkedFilePath) -> Self {
+        path.to_file_path_buf()
+    }
+}
+
+impl Drop for LinkedFilePathNode {
+    fn drop(&mut self) {
+        // Avoid recursion when dropping long chains of uniquely owned nodes.
+        let mut parent = self.parent.take();
+        while let Some(node) = parent {
+            parent = Arc::into_inner(node.0).and_then(|mut node| node.parent.take());
+        }
+    }
+}
+
+impl PartialEq<Self> for LinkedFilePath {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other)
+            || (self.len() == other.len()
+                && self.depth() == other.depth()
+                && Iterator::eq(self.components_rev(), other.components_rev()))
+    }
+}
+
+impl Eq for LinkedFilePath {}
+
+impl PartialEq<FilePath> for LinkedFilePath {
+    fn eq(&self, other: &FilePath) -> bool {
+        Iterator::eq(self.components_rev(), other.components().rev())
+    }
+}
+
+impl PartialEq<LinkedFilePath> for FilePath {
+    fn eq(&self, other: &LinkedFilePath) -> bool {
+        other == self
+    }
+}
+
+impl Hash for LinkedFilePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Same as `FilePath`.
+        let Ok(()) = self.try_for_each_component(&mut |component| {
+            state.write(component.as_bytes());
+            Ok::<_, Infallible>(())
+        });
+    }
+}
+
+impl Display for LinkedFilePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut first = true;
+        self.try_for_each_component(&mut |component| {
+            if !first {
+                SEPARATOR_CHAR.fmt(f)?;
+            }
+            first = false;
+            component.fmt(f)
+        })
+    }
+}
+
+impl core::fmt::Debug for LinkedFilePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("LinkedFilePath")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+/// Iterator over the components of a [`LinkedFilePath`], leaf to root.
+pub struct LinkedFilePathIter<'a>(Option<&'a LinkedFilePath>);
+
+impl<'a> Iterator for LinkedFilePathIter<'a> {
+    type Item = &'a FileName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.map(|path| {
+            self.0 = path.parent();
+            path.file_name()
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.map_or(0, LinkedFilePath::depth);
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for LinkedFilePathIter<'a> {}
+
+impl<'a> FusedIterator for LinkedFilePathIter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> &FileName {
+        FileName::new(name).unwrap()
+    }
+
+    fn hash<H: Hash + ?Sized>(h: &H) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        h.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn linked() {
+        let foo = LinkedFilePath::new(name("foo"));
+        let bar = foo.join(name("bar")).unwrap();
+        let baz = bar.join(name("baz.txt")).unwrap();
+        let bill = bar.join(name("bill")).unwrap();
+
+        assert_eq!(baz.len(), "foo/bar/baz.txt".len());
+        assert_eq!(baz.depth(), 3);
+        assert_eq!(baz.file_name().as_str(), "baz.txt");
+        assert!(baz.parent().unwrap().ptr_eq(&bar));
+        assert!(baz.parent().unwrap().ptr_eq(bill.parent().unwrap()));
+        assert!(foo.parent().is_none());
+
+        assert_eq!(baz.to_file_path_buf().as_str(), "foo/bar/baz.txt");
+        assert_eq!(foo.to_file_path_buf().as_str(), "foo");
+        assert_eq!(baz.to_string(), "foo/bar/baz.txt");
+        assert_eq!(
+            baz.components_rev()
+                .map(FileName::as_str)
+                .collect::<Vec<_>>(),
+            ["baz.txt", "bar", "foo"]
+        );
+
+        let path = FilePath::new("foo//bar/baz.txt").unwrap();
+        let baz_ = LinkedFilePath::from(path);
+        assert!(!baz_.ptr_eq(&baz));
+        assert_eq!(baz_, baz);
+        assert_ne!(baz, bill);
+        assert_ne!(bar, foo);
+        assert_eq!(baz, *path);
+        assert_eq!(*path, baz);
+        assert_eq!(hash(&baz), hash(path));
+        assert_eq!(hash(&baz), hash(&baz_));
+    }
+
+    #[test]
+    fn too_long() {
+        let mut path = LinkedFilePath::new(name("foo"));
+        while path.len() + 2 <= MAX_PATH_LEN {
+            path = path.join(name("a")).unwrap();
+        }
+        assert_eq!(
+            path.join(name("a")).err().unwrap(),
+            FilePathError::PathTooLong(path.len() + 2)
+        );
+        let path_buf = path.to_file_path_buf();
+        assert_eq!(path.to_string(), path_buf.as_str());
+        assert_eq!(hash(&path), hash(&path_buf));
+        // Does not overflow the stack.
+        drop(path);
+    }
+}
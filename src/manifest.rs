@@ -0,0 +1,402 @@
+use {
+    crate::*,
+    alloc::collections::{BTreeMap, btree_map},
+    core::{
+        error::Error,
+        fmt::{Display, Formatter},
+        iter::FusedIterator,
+    },
+};
+
+/// Options of [`Manifest::parse_with`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct ManifestParseOptions {
+    /// Also reject paths which only differ in case from a previous path
+    /// (e.g. `"Textures/Barrel.png"` and `"textures/barrel.png"`), which would collide on case-insensitive file systems.
+    pub reject_case_duplicates: bool,
+}
+
+/// Manifest entry data: the hash and the size of a file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ManifestEntry {
+    /// Content hash of the file, as a non-empty string of lowercase ASCII hex digits.
+    pub hash: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+}
+
+/// A text manifest of files, one `<hash> <size> <path>` entry per line, where
+/// - `<hash>` is a non-empty string of ASCII hex digits, of either case (normalized to lowercase),
+/// - `<size>` is the decimal size of the file in bytes,
+/// - `<path>` is the rest of the line, a (maybe non-canonical) [`FilePath`], which may contain spaces.
+///
+/// Entries are unique and sorted by path (componentwise), and are [`formatted`](Display) one per line,
+/// using the canonical string representation of the paths; empty lines are ignored by the parser.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{FilePath, Manifest};
+///
+/// let manifest = Manifest::parse("00ff 7 textures//barrel.png\n0a0b 3 meshes/barrel.fbx\n").unwrap();
+/// assert_eq!(manifest.get(FilePath::new("textures/barrel.png").unwrap()).unwrap().size, 7);
+/// assert_eq!(manifest.to_string(), "0a0b 3 meshes/barrel.fbx\n00ff 7 textures/barrel.png\n");
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Manifest {
+    entries: BTreeMap<FilePathBuf, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Creates an empty [`Manifest`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tries to parse the manifest `text`, rejecting duplicate paths.
+    ///
+    /// Returns an [`error`](ManifestError) with the line and column of the first invalid entry, if any.
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        Self::parse_with(text, ManifestParseOptions::default())
+    }
+
+    /// Tries to parse the manifest `text` with the parse `options`, rejecting duplicate paths.
+    ///
+    /// Returns an [`error`](ManifestError) with the line and column of the first invalid entry, if any.
+    pub fn parse_with(text: &str, options: ManifestParseOptions) -> Result<Self, ManifestError> {
+        let mut manifest = Self::new();
+        // Lowercase path -> path.
+        let mut lowercase = BTreeMap::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |offset: usize, kind| ManifestError {
+                line: idx + 1,
+                column: line[..offset].chars().count() + 1,
+                kind,
+            };
+
+            let (hash, rest) = line
+                .split_once(' ')
+                .ok_or_else(|| error(0, ManifestErrorKind::InvalidFormat))?;
+            let (size, path) = rest
+                .split_once(' ')
+                .ok_or_else(|| error(line.len(), ManifestErrorKind::InvalidFormat))?;
+            let size_offset = hash.len() + 1;
+            let path_offset = size_offset + size.len() + 1;
+
+            if !is_valid_hash(hash) {
+                return Err(error(0, ManifestErrorKind::InvalidHash));
+            }
+            let size = parse_size(size)
+                .ok_or_else(|| error(size_offset, ManifestErrorKind::InvalidSize))?;
+            let path = FilePathBuf::new(path)
+                .map_err(|err| error(path_offset, ManifestErrorKind::InvalidPath(err)))?;
+
+            if manifest.entries.contains_key(&path) {
+                return Err(error(path_offset, ManifestErrorKind::DuplicatePath(path)));
+            }
+            if options.reject_case_duplicates {
+                match lowercase.entry(path.as_str().to_lowercase()) {
+                    btree_map::Entry::Occupied(entry) => {
+                        return Err(error(
+                            path_offset,
+                            ManifestErrorKind::CaseDuplicatePath(FilePathBuf::clone(entry.get())),
+                        ));
+                    }
+                    btree_map::Entry::Vacant(entry) => {
+                        entry.insert(path.clone());
+                    }
+                }
+            }
+
+            manifest.entries.insert(
+                path,
+                ManifestEntry {
+                    hash: hash.to_ascii_lowercase(),
+                    size,
+                },
+            );
+        }
+
+        Ok(manifest)
+    }
+
+    /// Returns the number of entries in the [`Manifest`].
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry data of the `path`, if it is in the [`Manifest`].
+    pub fn get(&self, path: &FilePath) -> Option<&ManifestEntry> {
+        self.entries.get(path)
+    }
+
+    /// Inserts the `entry` at `path`, returning the previous entry data of the `path`, if any.
+    ///
+    /// The `entry`'s hash is normalized to lowercase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `entry`'s hash is not a non-empty string of ASCII hex digits.
+    pub fn insert(&mut self, path: FilePathBuf, mut entry: ManifestEntry) -> Option<ManifestEntry> {
+        assert!(
+            is_valid_hash(&entry.hash),
+            "manifest entry hashes must be non-empty strings of ASCII hex digits"
+        );
+        entry.hash.make_ascii_lowercase();
+        self.entries.insert(path, entry)
+    }
+
+    /// Removes the `path` from the [`Manifest`], returning its entry data, if any.
+    pub fn remove(&mut self, path: &FilePath) -> Option<ManifestEntry> {
+        self.entries.remove(path)
+    }
+
+    /// Returns an [`iterator`](ManifestIter) over the entries of the [`Manifest`], sorted by path (componentwise).
+    pub fn iter(&self) -> ManifestIter<'_> {
+        ManifestIter(self.entries.iter())
+    }
+}
+
+impl Display for Manifest {
+    /// Formats the [`Manifest`] as `<hash> <size> <path>` lines, sorted by path, with canonical paths.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (path, entry) in self.iter() {
+            writeln!(f, "{} {} {}", entry.hash, entry.size, path)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Manifest {
+    type Item = (&'a FilePath, &'a ManifestEntry);
+    type IntoIter = ManifestIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`Manifest`], sorted by path (componentwise).
+pub struct ManifestIter<'a>(btree_map::Iter<'a, FilePathBuf, ManifestEntry>);
+
+impl<'a> Iterator for ManifestIter<'a> {
+    type Item = (&'a FilePath, &'a ManifestEntry);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|(path, entry)| (path.as_file_path(), entry))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for ManifestIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|(path, entry)| (path.as_file_path(), entry))
+    }
+}
+
+impl<'a> ExactSizeIterator for ManifestIter<'a> {}
+
+impl<'a> FusedIterator for ManifestIter<'a> {}
+
+/// An error returned by [`Manifest::parse`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ManifestError {
+    /// Line number (1-based) of the invalid entry.
+    pub line: usize,
+    /// Column number (1-based, in characters) of the start of the invalid field of the entry.
+    pub column: usize,
+    pub kind: ManifestErrorKind,
+}
+
+/// The kind of a [`ManifestError`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ManifestErrorKind {
+    /// Line does not have the `<hash> <size> <path>` format.
+    InvalidFormat,
+    /// Hash is not a non-empty string of ASCII hex digits.
+    InvalidHash,
+    /// Size is not a decimal `u64`.
+    InvalidSize,
+    /// Path is not a valid [`FilePath`].
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+    /// Path is duplicated.
+    /// Contains the path.
+    DuplicatePath(FilePathBuf),
+    /// Path only differs in case from a previous path.
+    /// Contains the previous path.
+    CaseDuplicatePath(FilePathBuf),
+}
+
+impl Error for ManifestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ManifestErrorKind::InvalidPath(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use ManifestErrorKind::*;
+
+        write!(f, "manifest line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            InvalidFormat => "expected `<hash> <size> <path>`".fmt(f),
+            InvalidHash => "invalid hash".fmt(f),
+            InvalidSize => "invalid size".fmt(f),
+            InvalidPath(err) => write!(f, "invalid path: {err}"),
+            DuplicatePath(path) => write!(f, "duplicate path \"{path}\""),
+            CaseDuplicatePath(path) => write!(f, "path differs only in case from \"{path}\""),
+        }
+    }
+}
+
+fn is_valid_hash(hash: &str) -> bool {
+    !hash.is_empty() && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn parse_size(size: &str) -> Option<u64> {
+    // `u64::from_str` also accepts a leading `+`.
+    if size.bytes().all(|b| b.is_ascii_digit()) {
+        size.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest() {
+        let text = "\
+FF00 12 textures/props/barrel red.png
+
+0a 0 readme.txt
+0b 3 textures/./crate.png
+";
+        let mut manifest = Manifest::parse(text).unwrap();
+        assert_eq!(manifest.len(), 3);
+        assert_eq!(
+            manifest.get(FilePath::new("textures/props/barrel red.png").unwrap()),
+            Some(&ManifestEntry {
+                hash: String::from("ff00"),
+                size: 12
+            })
+        );
+        assert_eq!(
+            manifest.to_string(),
+            "\
+0a 0 readme.txt
+0b 3 textures/crate.png
+ff00 12 textures/props/barrel red.png
+"
+        );
+        assert_eq!(Manifest::parse(&manifest.to_string()).unwrap(), manifest);
+
+        manifest.insert(
+            FilePathBuf::new("meshes/barrel.fbx").unwrap(),
+            ManifestEntry {
+                hash: String::from("1A"),
+                size: 1,
+            },
+        );
+        assert_eq!(
+            manifest
+                .get(FilePath::new("meshes/barrel.fbx").unwrap())
+                .unwrap()
+                .hash,
+            "1a"
+        );
+        assert!(
+            manifest
+                .remove(FilePath::new("readme.txt").unwrap())
+                .is_some()
+        );
+        assert_eq!(
+            manifest
+                .iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "meshes/barrel.fbx",
+                "textures/crate.png",
+                "textures/props/barrel red.png"
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let error = |text, line, column, kind| {
+            assert_eq!(
+                Manifest::parse(text).err().unwrap(),
+                ManifestError { line, column, kind }
+            );
+        };
+
+        error("00 1", 1, 5, ManifestErrorKind::InvalidFormat);
+        error("00", 1, 1, ManifestErrorKind::InvalidFormat);
+        error("0x0 1 a", 1, 1, ManifestErrorKind::InvalidHash);
+        error(" 1 a", 1, 1, ManifestErrorKind::InvalidHash);
+        error("00 +1 a", 1, 4, ManifestErrorKind::InvalidSize);
+        error("00 1 a\nβ 1 b", 2, 1, ManifestErrorKind::InvalidHash);
+        error(
+            "00 1 a\n00 18446744073709551616 b",
+            2,
+            4,
+            ManifestErrorKind::InvalidSize,
+        );
+        error(
+            "00 1 foo/../bar",
+            1,
+            6,
+            ManifestErrorKind::InvalidPath(FilePathError::ParentDirectory(ErrorPath::from("foo"))),
+        );
+        error(
+            "00 1 a/b\n01 2 a//b",
+            2,
+            6,
+            ManifestErrorKind::DuplicatePath(FilePathBuf::new("a/b").unwrap()),
+        );
+
+        let text = "00 1 Textures/Barrel.png\n01 2 textures/barrel.png";
+        assert_eq!(Manifest::parse(text).unwrap().len(), 2);
+        assert_eq!(
+            Manifest::parse_with(
+                text,
+                ManifestParseOptions {
+                    reject_case_duplicates: true
+                }
+            )
+            .err()
+            .unwrap(),
+            ManifestError {
+                line: 2,
+                column: 6,
+                kind: ManifestErrorKind::CaseDuplicatePath(
+                    FilePathBuf::new("Textures/Barrel.png").unwrap()
+                )
+            }
+        );
+    }
+}
@@ -0,0 +1,846 @@
+use {
+    crate::*,
+    alloc::collections::{BTreeMap, btree_map},
+    core::{
+        fmt::{Debug, Formatter},
+        iter::{FromIterator, FusedIterator, Iterator, Peekable},
+    },
+    ministr::NonEmptyStr,
+};
+
+/// An ordered map keyed by [`FilePath`]'s, stored as a trie of [`path components`](FileName).
+///
+/// In addition to the usual map operations, supports efficient queries on the file system hierarchy:
+/// - finding the [`longest prefix`](Self::longest_prefix) of a file path present in the map,
+/// - iterating over all entries [`under a directory`](Self::iter_prefix),
+/// - [`removing`](Self::remove_subtree) or [`renaming`](Self::rename_subtree) a whole directory,
+/// - listing the [`immediate children`](Self::children) of a directory.
+///
+/// Entries are iterated in the componentwise order of their keys, consistent with the [`Ord`] implementation of [`FilePath`].
+///
+/// Also see [`FilePathSet`].
+pub struct FilePathMap<V> {
+    root: Node<V>,
+    len: usize,
+}
+
+struct Node<V> {
+    value: Option<V>,
+    children: BTreeMap<Box<str>, Node<V>>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Self {
+            value: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.is_empty()
+    }
+
+    fn get<'a, 'c, I: Iterator<Item = &'c FileName>>(&self, components: I) -> Option<&Self> {
+        let mut node = self;
+        for component in components {
+            node = node.children.get(component.as_str())?;
+        }
+        Some(node)
+    }
+
+    fn get_mut<'c, I: Iterator<Item = &'c FileName>>(
+        &mut self,
+        components: I,
+    ) -> Option<&mut Self> {
+        let mut node = self;
+        for component in components {
+            node = node.children.get_mut(component.as_str())?;
+        }
+        Some(node)
+    }
+
+    fn get_or_insert<'c, I: Iterator<Item = &'c FileName>>(&mut self, components: I) -> &mut Self {
+        let mut node = self;
+        for component in components {
+            node = node
+                .children
+                .entry(component.as_str().into())
+                .or_insert_with(Node::new);
+        }
+        node
+    }
+
+    /// Detaches and returns the node at the path formed by `components`, if any, removing the empty parent nodes.
+    fn detach<'c, I: Iterator<Item = &'c FileName>>(
+        &mut self,
+        components: &mut Peekable<I>,
+    ) -> Option<Self> {
+        let component = components.next()?;
+
+        // Reached the leaf.
+        if components.peek().is_none() {
+            return self.children.remove(component.as_str());
+        }
+
+        let child = self.children.get_mut(component.as_str())?;
+        let node = child.detach(components)?;
+
+        if child.is_empty() {
+            self.children.remove(component.as_str());
+        }
+
+        Some(node)
+    }
+
+    /// Removes and returns the value at the path formed by `components`, if any, removing the empty nodes.
+    fn remove<'c, I: Iterator<Item = &'c FileName>>(&mut self, components: &mut I) -> Option<V> {
+        let Some(component) = components.next() else {
+            return self.value.take();
+        };
+
+        let child = self.children.get_mut(component.as_str())?;
+        let value = child.remove(components);
+
+        if child.is_empty() {
+            self.children.remove(component.as_str());
+        }
+
+        value
+    }
+
+    /// Returns the number of values in the subtree.
+    fn count(&self) -> usize {
+        self.value.is_some() as usize + self.children.values().map(Node::count).sum::<usize>()
+    }
+
+    /// Returns the length in bytes of the longest path in the subtree, relative to this node.
+    fn max_path_len(&self) -> usize {
+        self.children
+            .iter()
+            .map(|(component, child)| {
+                let child_len = child.max_path_len();
+                // Count the separator.
+                component.len() + if child_len > 0 { child_len + 1 } else { 0 }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<V: Clone> Clone for Node<V> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            children: self.children.clone(),
+        }
+    }
+}
+
+impl<V> FilePathMap<V> {
+    /// Creates an empty [`FilePathMap`].
+    pub fn new() -> Self {
+        Self {
+            root: Node::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all entries from the map.
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.len = 0;
+    }
+
+    /// Inserts the `value` at the `path`.
+    ///
+    /// Returns the previous value at the `path`, if any.
+    pub fn insert(&mut self, path: &FilePath, value: V) -> Option<V> {
+        let prev_value = self
+            .root
+            .get_or_insert(path.components())
+            .value
+            .replace(value);
+        if prev_value.is_none() {
+            self.len += 1;
+        }
+        prev_value
+    }
+
+    /// Returns the value at the `path`, if any.
+    pub fn get(&self, path: &FilePath) -> Option<&V> {
+        self.root.get(path.components())?.value.as_ref()
+    }
+
+    /// Returns the value at the `path`, if any.
+    pub fn get_mut(&mut self, path: &FilePath) -> Option<&mut V> {
+        self.root.get_mut(path.components())?.value.as_mut()
+    }
+
+    pub fn contains_key(&self, path: &FilePath) -> bool {
+        self.get(path).is_some()
+    }
+
+    /// Removes and returns the value at the `path`, if any.
+    ///
+    /// Does not affect the entries under the `path`.
+    pub fn remove(&mut self, path: &FilePath) -> Option<V> {
+        let value = self.root.remove(&mut path.components());
+        if value.is_some() {
+            self.len -= 1;
+        }
+        value
+    }
+
+    /// Finds the longest prefix of the `path` (including the `path` itself) which is present in the map.
+    ///
+    /// Returns the prefix (borrowed from the `path`) and its value, if any.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::*;
+    ///
+    /// let mut mounts = FilePathMap::new();
+    /// mounts.insert(FilePath::new("data").unwrap(), 0);
+    /// mounts.insert(FilePath::new("data/dlc").unwrap(), 1);
+    ///
+    /// let (prefix, mount) = mounts.longest_prefix(FilePath::new("data/dlc/textures/a.png").unwrap()).unwrap();
+    /// assert_eq!((prefix.as_str(), *mount), ("data/dlc", 1));
+    ///
+    /// let (prefix, mount) = mounts.longest_prefix(FilePath::new("data/dlc2").unwrap()).unwrap();
+    /// assert_eq!((prefix.as_str(), *mount), ("data", 0));
+    ///
+    /// assert!(mounts.longest_prefix(FilePath::new("config/a.cfg").unwrap()).is_none());
+    /// ```
+    pub fn longest_prefix<'p>(&self, path: &'p FilePath) -> Option<(&'p FilePath, &V)> {
+        let mut node = &self.root;
+        let mut longest_prefix = None;
+
+        for component in path.components() {
+            let Some(child) = node.children.get(component.as_str()) else {
+                break;
+            };
+            node = child;
+
+            if let Some(value) = node.value.as_ref() {
+                // Path components are borrowed from the `path` string.
+                let prefix_len =
+                    component.as_ptr() as usize - path.as_str().as_ptr() as usize + component.len();
+                longest_prefix = Some((prefix_len, value));
+            }
+        }
+
+        longest_prefix.map(|(prefix_len, value)| {
+            // Prefix of a valid file path which ends on a component boundary is also a valid file path.
+            (
+                unsafe {
+                    FilePath::from_str(NonEmptyStr::new_unchecked(
+                        path.as_str().get_unchecked(..prefix_len),
+                    ))
+                },
+                value,
+            )
+        })
+    }
+
+    /// Returns an [`iterator`](FilePathMapIter) over all the entries of the map, in order.
+    pub fn iter(&self) -> FilePathMapIter<'_, V> {
+        FilePathMapIter::new(&self.root, String::new(), self.len)
+    }
+
+    /// Returns an iterator over all the keys of the map, in order.
+    pub fn keys(&self) -> impl Iterator<Item = FilePathBuf> + '_ {
+        self.iter().map(|(path, _)| path)
+    }
+
+    /// Returns an iterator over all the values of the map, in key order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an [`iterator`](FilePathMapIter) over all the entries of the map at or under the directory `prefix`, in order.
+    pub fn iter_prefix(&self, prefix: &FilePath) -> FilePathMapIter<'_, V> {
+        if let Some(node) = self.root.get(prefix.components()) {
+            FilePathMapIter::new(node, prefix.to_owned().into_string(), usize::MAX)
+        } else {
+            FilePathMapIter::empty()
+        }
+    }
+
+    /// Returns an iterator over the immediate children of the directory `dir`, or of the root if `dir` is `None`, in order.
+    ///
+    /// Yields the child's file name and the value at the child path, if any
+    /// (children with no value are the parent directories of some entries in the map).
+    pub fn children(
+        &self,
+        dir: Option<&FilePath>,
+    ) -> impl Iterator<Item = (&FileName, Option<&V>)> {
+        let node = match dir {
+            Some(dir) => self.root.get(dir.components()),
+            None => Some(&self.root),
+        };
+
+        node.into_iter().flat_map(|node| {
+            node.children.iter().map(|(component, child)| {
+                (
+                    // Only contains (non-empty) valid path components.
+                    unsafe { FileName::from_ne_str(NonEmptyStr::new_unchecked(component)) },
+                    child.value.as_ref(),
+                )
+            })
+        })
+    }
+
+    /// Removes all the entries at or under the directory `prefix`.
+    ///
+    /// Returns the removed entries (with unchanged keys) as a new map.
+    pub fn remove_subtree(&mut self, prefix: &FilePath) -> Self {
+        let mut removed = Self::new();
+
+        if let Some(node) = self.root.detach(&mut prefix.components().peekable()) {
+            let len = node.count();
+            self.len -= len;
+
+            *removed.root.get_or_insert(prefix.components()) = node;
+            removed.len = len;
+        }
+
+        removed
+    }
+
+    /// Moves all the entries at or under the directory `from` to the directory `to`,
+    /// i.e. renames the `from` directory to `to`.
+    ///
+    /// Returns `false` and leaves the map unchanged if
+    /// - there are no entries at or under `from`,
+    /// - there are entries at or under `to`, or `to` is a parent directory of some entries in the map,
+    /// - `to` is at or under `from`,
+    /// - any of the renamed keys would become longer than [`MAX_PATH_LEN`].
+    pub fn rename_subtree(&mut self, from: &FilePath, to: &FilePath) -> bool {
+        if to.starts_with(from) || self.root.get(to.components()).is_some() {
+            return false;
+        }
+
+        let Some(node) = self.root.get(from.components()) else {
+            return false;
+        };
+
+        let child_len = node.max_path_len();
+        // Count the separator.
+        let max_path_len = canonical_len(to) + if child_len > 0 { child_len + 1 } else { 0 };
+        if max_path_len > MAX_PATH_LEN {
+            return false;
+        }
+
+        let node = unsafe {
+            self.root
+                .detach(&mut from.components().peekable())
+                .unwrap_unchecked_dbg_msg("`from` must be in the map")
+        };
+        *self.root.get_or_insert(to.components()) = node;
+
+        true
+    }
+}
+
+impl<V> Default for FilePathMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone> Clone for FilePathMap<V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<V: Debug> Debug for FilePathMap<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: PartialEq> PartialEq for FilePathMap<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Eq> Eq for FilePathMap<V> {}
+
+impl<'p, V> FromIterator<(&'p FilePath, V)> for FilePathMap<V> {
+    fn from_iter<I: IntoIterator<Item = (&'p FilePath, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'p, V> Extend<(&'p FilePath, V)> for FilePathMap<V> {
+    fn extend<I: IntoIterator<Item = (&'p FilePath, V)>>(&mut self, iter: I) {
+        for (path, value) in iter {
+            self.insert(path, value);
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a FilePathMap<V> {
+    type Item = (FilePathBuf, &'a V);
+    type IntoIter = FilePathMapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+type ChildrenIter<'a, V> = btree_map::Iter<'a, Box<str>, Node<V>>;
+
+/// Iterator over the entries of a [`FilePathMap`], in order.
+///
+/// Yields the (owned) file paths of the entries and the references to their values.
+pub struct FilePathMapIter<'a, V> {
+    /// Value of the subtree root node, if any, yielded first.
+    first: Option<&'a V>,
+    /// Iterators over the children of the current node and its parents,
+    /// and the lengths of their file path strings.
+    stack: Vec<(ChildrenIter<'a, V>, usize)>,
+    /// Path of the last yielded entry.
+    path: String,
+    /// Number of entries left, if known, or `usize::MAX`.
+    len: usize,
+}
+
+impl<'a, V> FilePathMapIter<'a, V> {
+    /// `path` is the canonical file path of the subtree root `node`, or empty for the map root.
+    fn new(node: &'a Node<V>, path: String, len: usize) -> Self {
+        let path_len = path.len();
+        Self {
+            first: if path.is_empty() {
+                None
+            } else {
+                node.value.as_ref()
+            },
+            stack: vec![(node.children.iter(), path_len)],
+            path,
+            len,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            first: None,
+            stack: Vec::new(),
+            path: String::new(),
+            len: 0,
+        }
+    }
+
+    fn path(&self) -> FilePathBuf {
+        // Only contains valid canonical file paths.
+        FilePathBuf(unsafe { ministr::NonEmptyString::new_unchecked(self.path.clone()) })
+    }
+
+    fn yielded(&mut self) {
+        if self.len != usize::MAX {
+            self.len -= 1;
+        }
+    }
+}
+
+impl<'a, V> Iterator for FilePathMapIter<'a, V> {
+    type Item = (FilePathBuf, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(value) = self.first.take() {
+            self.yielded();
+            return Some((self.path(), value));
+        }
+
+        loop {
+            let (children, parent_len) = self.stack.last_mut()?;
+            let parent_len = *parent_len;
+
+            if let Some((component, child)) = children.next() {
+                self.path.truncate(parent_len);
+                if parent_len != 0 {
+                    self.path.push(SEPARATOR_CHAR);
+                }
+                self.path.push_str(component);
+
+                self.stack.push((child.children.iter(), self.path.len()));
+
+                if let Some(value) = child.value.as_ref() {
+                    self.yielded();
+                    return Some((self.path(), value));
+                }
+            } else {
+                self.stack.pop();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.len == usize::MAX {
+            (self.first.is_some() as usize, None)
+        } else {
+            (self.len, Some(self.len))
+        }
+    }
+}
+
+impl<'a, V> FusedIterator for FilePathMapIter<'a, V> {}
+
+/// An ordered set of [`FilePath`]'s, stored as a trie of [`path components`](FileName).
+///
+/// See [`FilePathMap`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct FilePathSet(FilePathMap<()>);
+
+impl FilePathSet {
+    /// Creates an empty [`FilePathSet`].
+    pub fn new() -> Self {
+        Self(FilePathMap::new())
+    }
+
+    /// Returns the number of file paths in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes all file paths from the set.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Adds the `path` to the set.
+    ///
+    /// Returns `true` if the `path` was not in the set.
+    pub fn insert(&mut self, path: &FilePath) -> bool {
+        self.0.insert(path, ()).is_none()
+    }
+
+    pub fn contains(&self, path: &FilePath) -> bool {
+        self.0.contains_key(path)
+    }
+
+    /// Removes the `path` from the set.
+    ///
+    /// Returns `true` if the `path` was in the set.
+    /// Does not affect the file paths under the `path`.
+    pub fn remove(&mut self, path: &FilePath) -> bool {
+        self.0.remove(path).is_some()
+    }
+
+    /// See [`FilePathMap::longest_prefix`].
+    pub fn longest_prefix<'p>(&self, path: &'p FilePath) -> Option<&'p FilePath> {
+        self.0.longest_prefix(path).map(|(prefix, _)| prefix)
+    }
+
+    /// Returns an iterator over all the file paths in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = FilePathBuf> + '_ {
+        self.0.keys()
+    }
+
+    /// Returns an iterator over all the file paths in the set at or under the directory `prefix`, in order.
+    pub fn iter_prefix(&self, prefix: &FilePath) -> impl Iterator<Item = FilePathBuf> + '_ {
+        self.0.iter_prefix(prefix).map(|(path, _)| path)
+    }
+
+    /// Returns an iterator over the immediate children of the directory `dir`, or of the root if `dir` is `None`, in order.
+    ///
+    /// Yields the child's file name and whether the child path is in the set
+    /// (children not in the set are the parent directories of some file paths in the set).
+    pub fn children(&self, dir: Option<&FilePath>) -> impl Iterator<Item = (&FileName, bool)> {
+        self.0
+            .children(dir)
+            .map(|(component, value)| (component, value.is_some()))
+    }
+
+    /// See [`FilePathMap::remove_subtree`].
+    pub fn remove_subtree(&mut self, prefix: &FilePath) -> Self {
+        Self(self.0.remove_subtree(prefix))
+    }
+
+    /// See [`FilePathMap::rename_subtree`].
+    pub fn rename_subtree(&mut self, from: &FilePath, to: &FilePath) -> bool {
+        self.0.rename_subtree(from, to)
+    }
+}
+
+impl Debug for FilePathSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'p> FromIterator<&'p FilePath> for FilePathSet {
+    fn from_iter<I: IntoIterator<Item = &'p FilePath>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'p> Extend<&'p FilePath> for FilePathSet {
+    fn extend<I: IntoIterator<Item = &'p FilePath>>(&mut self, iter: I) {
+        for path in iter {
+            self.insert(path);
+        }
+    }
+}
+
+/// Returns the length in bytes of the canonical string representation of the `path`.
+fn canonical_len(path: &FilePath) -> usize {
+    path.components()
+        .map(|component| component.len() + 1)
+        .sum::<usize>()
+        - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> &FilePath {
+        FilePath::new(path).unwrap()
+    }
+
+    fn keys<V>(iter: impl Iterator<Item = (FilePathBuf, V)>) -> Vec<String> {
+        iter.map(|(path, _)| path.into_string()).collect()
+    }
+
+    fn map() -> FilePathMap<u32> {
+        ["a-b", "a/b/c", "a/b", "b", "a/b/d/e", "a/bc"]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, p)| (path(p), idx as u32))
+            .collect()
+    }
+
+    #[test]
+    fn map_basic() {
+        let mut map = map();
+        assert_eq!(map.len(), 6);
+
+        assert_eq!(map.get(path("a/b")), Some(&2));
+        assert_eq!(map.get(path("a//b/")), Some(&2));
+        assert_eq!(map.get(path("a")), None);
+        assert_eq!(map.get(path("a/b/d")), None);
+        assert_eq!(map.get(path("c")), None);
+
+        assert_eq!(map.insert(path("a/b"), 7), Some(2));
+        assert_eq!(map.len(), 6);
+        *map.get_mut(path("a/b")).unwrap() += 1;
+        assert_eq!(map.get(path("a/b")), Some(&8));
+
+        assert_eq!(map.remove(path("a/b/d")), None);
+        assert_eq!(map.remove(path("a/b/d/e")), Some(4));
+        assert_eq!(map.len(), 5);
+        assert!(!map.contains_key(path("a/b/d/e")));
+        // Empty parent nodes were removed.
+        assert_eq!(
+            map.children(Some(path("a/b")))
+                .map(|(c, _)| c.as_str())
+                .collect::<Vec<_>>(),
+            ["c"]
+        );
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.iter().count(), 0);
+    }
+
+    #[test]
+    fn map_iter() {
+        let map = map();
+
+        assert_eq!(
+            keys(map.iter()),
+            ["a/b", "a/b/c", "a/b/d/e", "a/bc", "a-b", "b"]
+        );
+        assert_eq!(map.iter().size_hint(), (6, Some(6)));
+        assert_eq!(
+            map.values().copied().collect::<Vec<_>>(),
+            [2, 1, 4, 5, 0, 3]
+        );
+
+        // Same order as `FilePathBuf`'s.
+        let mut sorted: Vec<_> = map.keys().collect();
+        sorted.sort();
+        assert_eq!(sorted, map.keys().collect::<Vec<_>>());
+
+        assert_eq!(
+            keys(map.iter_prefix(path("a/b"))),
+            ["a/b", "a/b/c", "a/b/d/e"]
+        );
+        assert_eq!(
+            keys(map.iter_prefix(path("a"))),
+            ["a/b", "a/b/c", "a/b/d/e", "a/bc"]
+        );
+        assert_eq!(keys(map.iter_prefix(path("a/b/d"))), ["a/b/d/e"]);
+        assert!(keys(map.iter_prefix(path("c"))).is_empty());
+    }
+
+    #[test]
+    fn map_children() {
+        let map = map();
+
+        assert_eq!(
+            map.children(None)
+                .map(|(c, v)| (c.as_str(), v.copied()))
+                .collect::<Vec<_>>(),
+            [("a", None), ("a-b", Some(0)), ("b", Some(3))]
+        );
+        assert_eq!(
+            map.children(Some(path("a/b")))
+                .map(|(c, v)| (c.as_str(), v.copied()))
+                .collect::<Vec<_>>(),
+            [("c", Some(1)), ("d", None)]
+        );
+        assert_eq!(map.children(Some(path("b"))).count(), 0);
+        assert_eq!(map.children(Some(path("x"))).count(), 0);
+    }
+
+    #[test]
+    fn map_longest_prefix() {
+        let map = map();
+
+        let (prefix, value) = map.longest_prefix(path("a/b/d/e/f.txt")).unwrap();
+        assert_eq!((prefix.as_str(), *value), ("a/b/d/e", 4));
+
+        let (prefix, value) = map.longest_prefix(path("a/b/d/f.txt")).unwrap();
+        assert_eq!((prefix.as_str(), *value), ("a/b", 2));
+
+        // Non-canonical paths.
+        let (prefix, value) = map.longest_prefix(path("a//./b/d/f.txt")).unwrap();
+        assert_eq!((prefix.as_str(), *value), ("a//./b", 2));
+
+        let (prefix, value) = map.longest_prefix(path("b")).unwrap();
+        assert_eq!((prefix.as_str(), *value), ("b", 3));
+
+        assert!(map.longest_prefix(path("a/x")).is_none());
+        assert!(map.longest_prefix(path("c")).is_none());
+    }
+
+    #[test]
+    fn map_subtree() {
+        let mut map = map();
+
+        let removed = map.remove_subtree(path("a/b"));
+        assert_eq!(keys(removed.iter()), ["a/b", "a/b/c", "a/b/d/e"]);
+        assert_eq!(removed.len(), 3);
+        assert_eq!(keys(map.iter()), ["a/bc", "a-b", "b"]);
+        assert_eq!(map.len(), 3);
+
+        assert!(map.remove_subtree(path("x")).is_empty());
+
+        let mut map = self::map();
+
+        assert!(map.rename_subtree(path("a/b"), path("c/d")));
+        assert_eq!(
+            keys(map.iter()),
+            ["a/bc", "a-b", "b", "c/d", "c/d/c", "c/d/d/e"]
+        );
+        assert_eq!(map.get(path("c/d/d/e")), Some(&4));
+        assert_eq!(map.len(), 6);
+
+        // Source does not exist.
+        assert!(!map.rename_subtree(path("a/b"), path("e")));
+        // Destination exists.
+        assert!(!map.rename_subtree(path("c/d"), path("b")));
+        // Destination is a parent directory.
+        assert!(!map.rename_subtree(path("b"), path("c")));
+        // Destination is under the source.
+        assert!(!map.rename_subtree(path("c"), path("c/e")));
+        // Too long.
+        let mut long_path =
+            vec!["a".repeat(MAX_COMPONENT_LEN); MAX_PATH_LEN / (MAX_COMPONENT_LEN + 1)].join("/");
+        long_path.push('/');
+        // Longest renamed key is `"c/d/d/e"`.
+        let max_len = MAX_PATH_LEN - "/d/d/e".len();
+        let rest = max_len - long_path.len();
+
+        let max_path = FilePathBuf::new(format!("{}{}", long_path, "b".repeat(rest))).unwrap();
+        assert_eq!(max_path.len(), max_len);
+        assert!(map.rename_subtree(path("c"), &max_path));
+        assert!(map.rename_subtree(&max_path, path("c")));
+
+        let too_long_path =
+            FilePathBuf::new(format!("{}{}", long_path, "b".repeat(rest + 1))).unwrap();
+        assert!(!map.rename_subtree(path("c"), &too_long_path));
+
+        // A childless entry may be renamed to a path of exactly `MAX_PATH_LEN`.
+        let max_path = FilePathBuf::new(format!(
+            "{}{}",
+            long_path,
+            "b".repeat(MAX_PATH_LEN - long_path.len())
+        ))
+        .unwrap();
+        assert_eq!(max_path.len(), MAX_PATH_LEN);
+        assert!(map.rename_subtree(path("b"), &max_path));
+        assert_eq!(map.get(&max_path), Some(&3));
+        assert!(map.rename_subtree(&max_path, path("b")));
+
+        assert_eq!(
+            keys(map.iter()),
+            ["a/bc", "a-b", "b", "c/d", "c/d/c", "c/d/d/e"]
+        );
+    }
+
+    #[test]
+    fn set() {
+        let mut set: FilePathSet = ["a/b", "a/c", "d"].into_iter().map(path).collect();
+        assert_eq!(set.len(), 3);
+
+        assert!(!set.insert(path("a//b")));
+        assert!(set.insert(path("a/b/e")));
+        assert!(set.contains(path("a/b/e")));
+        assert!(!set.contains(path("a")));
+
+        assert_eq!(set.longest_prefix(path("a/b/f")).unwrap().as_str(), "a/b");
+
+        assert_eq!(
+            set.iter().map(FilePathBuf::into_string).collect::<Vec<_>>(),
+            ["a/b", "a/b/e", "a/c", "d"]
+        );
+        assert_eq!(
+            set.iter_prefix(path("a/b"))
+                .map(FilePathBuf::into_string)
+                .collect::<Vec<_>>(),
+            ["a/b", "a/b/e"]
+        );
+        assert_eq!(
+            set.children(None)
+                .map(|(c, v)| (c.as_str(), v))
+                .collect::<Vec<_>>(),
+            [("a", false), ("d", true)]
+        );
+
+        assert!(set.rename_subtree(path("a"), path("f")));
+        assert_eq!(set.remove_subtree(path("f/b")).len(), 2);
+        assert!(set.remove(path("f/c")));
+        assert!(!set.remove(path("f/c")));
+
+        assert_eq!(
+            format!("{:?}", set),
+            format!("{{{:?}}}", FilePathBuf::new("d").unwrap())
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use {
+    crate::*,
+    alloc::collections::BTreeMap,
+    core::{cmp::Ordering, iter::Iterator, ops::Bound},
+};
+
+/// Compares the file paths componentwise, ordering directories before files within the same parent directory.
+///
+/// Components are compared as strings, same as the default [`Ord`] implementation of [`FilePath`].
+/// The leaf component of a file path is always considered to be a file,
+/// so the contents of a directory are ordered before a file with the same name as the directory.
+///
+/// E.g. `"a/z/b.txt"` < `"a/a.txt"` < `"a/z"` < `"b.txt"`; `"a/b"` < `"a"`.
+///
+/// Can be used with e.g. [`slice::sort_by`].
+pub fn cmp_dirs_first(l: &FilePath, r: &FilePath) -> Ordering {
+    cmp_dirs_first_impl(l, r, |l, r| l.cmp(r))
+}
+
+/// Compares the file paths componentwise, comparing the components in "natural" order,
+/// i.e. comparing the runs of ASCII digits in path components numerically.
+///
+/// Path components which are equal numerically but differ in leading zeros are ordered as strings,
+/// which keeps the ordering consistent with [`Eq`].
+///
+/// E.g. `"frame2.png"` < `"frame10.png"`; `"v2/a"` < `"v10/a"`; `"frame01"` < `"frame1"` < `"frame2"`.
+///
+/// Can be used with e.g. [`slice::sort_by`].
+pub fn cmp_natural(l: &FilePath, r: &FilePath) -> Ordering {
+    let mut l = l.components();
+    let mut r = r.components();
+
+    loop {
+        match (l.next(), r.next()) {
+            (Some(l_comp), Some(r_comp)) => match cmp_natural_str(l_comp, r_comp) {
+                Ordering::Equal => {}
+                ord => return ord,
+            },
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+/// Returns an iterator over all the entries of the `map` whose keys are located under the directory `prefix`
+/// (not including the `prefix` itself), in order.
+///
+/// Relies on the componentwise [`Ord`] implementation of [`FilePathBuf`], which orders all file paths
+/// which start with the `prefix` directly after the `prefix`.
+/// Finds the first such entry in `O(log n)` time.
+///
+/// E.g.
+/// ```
+/// use {minifilepath::*, std::collections::BTreeMap};
+///
+/// let map: BTreeMap<_, _> = ["a-b", "a/b", "a/b/c", "a/b/d/e", "a/bc", "b"]
+///     .into_iter()
+///     .map(|path| (FilePathBuf::new(path).unwrap(), ()))
+///     .collect();
+///
+/// let keys: Vec<_> = prefix_range(&map, FilePath::new("a/b").unwrap())
+///     .map(|(key, _)| key.as_str())
+///     .collect();
+///
+/// assert_eq!(keys, ["a/b/c", "a/b/d/e"]);
+/// ```
+pub fn prefix_range<'a, V>(
+    map: &'a BTreeMap<FilePathBuf, V>,
+    prefix: &FilePath,
+) -> impl Iterator<Item = (&'a FilePathBuf, &'a V)> {
+    map.range::<FilePath, _>((Bound::Excluded(prefix), Bound::Unbounded))
+        .take_while(move |(key, _)| key.starts_with(prefix))
+}
+
+fn cmp_dirs_first_impl<C: Fn(&str, &str) -> Ordering>(
+    l: &FilePath,
+    r: &FilePath,
+    cmp: C,
+) -> Ordering {
+    let mut l = l.components().peekable();
+    let mut r = r.components().peekable();
+
+    // Compares the `(is_file, component)` pairs lexicographically, which is a total order.
+    loop {
+        match (l.next(), r.next()) {
+            (Some(l_comp), Some(r_comp)) => {
+                let l_is_dir = l.peek().is_some();
+                let r_is_dir = r.peek().is_some();
+
+                // Directories go first.
+                match r_is_dir.cmp(&l_is_dir).then_with(|| cmp(l_comp, r_comp)) {
+                    Ordering::Equal => {}
+                    ord => return ord,
+                }
+            }
+            // Equal components at the previous depth are either both directories or both files,
+            // so both paths end at the same depth.
+            (l, r) => return l.is_some().cmp(&r.is_some()),
+        }
+    }
+}
+
+/// Compares the strings, comparing the runs of ASCII digits numerically.
+/// Falls back to comparing the strings as is if they are equal numerically.
+fn cmp_natural_str(l: &str, r: &str) -> Ordering {
+    // Digits are ASCII and thus may never be a part of a multi-byte UTF-8 sequence,
+    // and comparing UTF-8 strings bytewise is equivalent to comparing them charwise.
+    let mut l_bytes = l.as_bytes();
+    let mut r_bytes = r.as_bytes();
+
+    while let (Some(l_byte), Some(r_byte)) = (l_bytes.first(), r_bytes.first()) {
+        let ord = if l_byte.is_ascii_digit() && r_byte.is_ascii_digit() {
+            let (l_digits, l_rest) = split_digits(l_bytes);
+            let (r_digits, r_rest) = split_digits(r_bytes);
+
+            l_bytes = l_rest;
+            r_bytes = r_rest;
+
+            let l_digits = trim_leading_zeros(l_digits);
+            let r_digits = trim_leading_zeros(r_digits);
+
+            // Longer numbers (without leading zeros) are larger.
+            l_digits
+                .len()
+                .cmp(&r_digits.len())
+                .then_with(|| l_digits.cmp(r_digits))
+        } else {
+            l_bytes = &l_bytes[1..];
+            r_bytes = &r_bytes[1..];
+
+            l_byte.cmp(r_byte)
+        };
+
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    l_bytes.len().cmp(&r_bytes.len()).then_with(|| l.cmp(r))
+}
+
+fn split_digits(bytes: &[u8]) -> (&[u8], &[u8]) {
+    let num_digits = bytes
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(bytes.len());
+    bytes.split_at(num_digits)
+}
+
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let num_zeros = digits
+        .iter()
+        .position(|b| *b != b'0')
+        .unwrap_or(digits.len());
+    &digits[num_zeros..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted<F: FnMut(&FilePathBuf, &FilePathBuf) -> Ordering>(
+        paths: &[&str],
+        cmp: F,
+    ) -> Vec<String> {
+        let mut paths: Vec<_> = paths
+            .iter()
+            .map(|path| FilePathBuf::new(path).unwrap())
+            .collect();
+        paths.sort_by(cmp);
+        paths.into_iter().map(FilePathBuf::into_string).collect()
+    }
+
+    #[test]
+    fn ord() {
+        let paths = ["a-b", "b", "a/b/c", "a/b", "a", "A"];
+        let expected = ["A", "a", "a/b", "a/b/c", "a-b", "b"];
+
+        assert_eq!(sorted(&paths, FilePathBuf::cmp), expected);
+        assert_eq!(
+            sorted(&paths, |l, r| l.as_file_path().cmp(r.as_file_path())),
+            expected
+        );
+
+        assert!(FilePath::new("a/b").unwrap() < FilePath::new("a-b").unwrap());
+        assert!(FilePathBuf::new("a/b").unwrap() < FilePathBuf::new("a-b").unwrap());
+        assert_eq!(
+            FilePath::new("a//b/")
+                .unwrap()
+                .cmp(FilePath::new("a/b").unwrap()),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn dirs_first() {
+        let paths = [
+            "b.txt",
+            "a/a.txt",
+            "a/z/b.txt",
+            "a",
+            "a/z",
+            "c/d",
+            "a/z/b.txt/c",
+        ];
+
+        assert_eq!(
+            sorted(&paths, |l, r| cmp_dirs_first(l, r)),
+            [
+                "a/z/b.txt/c",
+                "a/z/b.txt",
+                "a/a.txt",
+                "a/z",
+                "c/d",
+                "a",
+                "b.txt"
+            ]
+        );
+
+        // Must be a total order.
+        let paths: Vec<_> = paths
+            .iter()
+            .map(|path| FilePath::new(path).unwrap())
+            .collect();
+        for a in paths.iter() {
+            assert_eq!(cmp_dirs_first(a, a), Ordering::Equal);
+
+            for b in paths.iter() {
+                assert_eq!(cmp_dirs_first(a, b), cmp_dirs_first(b, a).reverse());
+
+                for c in paths.iter() {
+                    if cmp_dirs_first(a, b).is_le() && cmp_dirs_first(b, c).is_le() {
+                        assert!(
+                            cmp_dirs_first(a, c).is_le(),
+                            "{a} <= {b} <= {c}, but {a} > {c}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn natural() {
+        assert_eq!(
+            sorted(
+                &[
+                    "frame10.png",
+                    "frame2.png",
+                    "frame01.png",
+                    "frame1.png",
+                    "frame.png",
+                    "v10/a",
+                    "v2/b"
+                ],
+                |l, r| cmp_natural(l, r)
+            ),
+            [
+                "frame.png",
+                "frame01.png",
+                "frame1.png",
+                "frame2.png",
+                "frame10.png",
+                "v2/b",
+                "v10/a"
+            ]
+        );
+
+        assert_eq!(cmp_natural_str("a1b2", "a1b10"), Ordering::Less);
+        assert_eq!(cmp_natural_str("a10", "a9z"), Ordering::Greater);
+        assert_eq!(cmp_natural_str("a", "a0"), Ordering::Less);
+        assert_eq!(cmp_natural_str("007", "7"), Ordering::Less);
+        assert_eq!(cmp_natural_str("007a", "7b"), Ordering::Less);
+        assert_eq!(cmp_natural_str("7b", "007a"), Ordering::Greater);
+        assert_eq!(cmp_natural_str("7", "7"), Ordering::Equal);
+    }
+
+    #[test]
+    fn prefix_range_() {
+        let map: BTreeMap<_, _> = [
+            "a", "a-b", "a/b", "a/b/c", "a/b/d/e", "a/bc", "a/b.txt", "b",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, path)| (FilePathBuf::new(path).unwrap(), idx))
+        .collect();
+
+        let range = |prefix: &str| -> Vec<String> {
+            prefix_range(&map, FilePath::new(prefix).unwrap())
+                .map(|(key, _)| key.to_string())
+                .collect()
+        };
+
+        assert_eq!(range("a/b"), ["a/b/c", "a/b/d/e"]);
+        assert_eq!(range("a"), ["a/b", "a/b/c", "a/b/d/e", "a/b.txt", "a/bc"]);
+        assert!(range("a/b/c").is_empty());
+        assert!(range("c").is_empty());
+    }
+}
@@ -0,0 +1,667 @@
+use {
+    crate::*,
+    alloc::collections::{BTreeMap, BTreeSet},
+    core::{
+        fmt::{Display, Formatter},
+        ops::Bound,
+    },
+    std::{
+        fs,
+        io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    },
+};
+
+/// Magic bytes at the start and at the end of a pack file.
+pub const PACK_MAGIC: [u8; 4] = *b"MFPK";
+
+/// Current (and only supported) pack file format version.
+pub const PACK_VERSION: u32 = 1;
+
+/// Size in bytes of the pack file header: [`PACK_MAGIC`] and the little-endian `u32` [`PACK_VERSION`].
+const HEADER_SIZE: u64 = 8;
+
+/// Size in bytes of the pack file footer: the little-endian `u64` index offset,
+/// the little-endian `u32` entry count and [`PACK_MAGIC`].
+const FOOTER_SIZE: u64 = 16;
+
+/// Index entry flag set if the entry has a content hash.
+const HAS_HASH: u8 = 0x1;
+
+/// An error returned by [`PackReader`] / [`PackWriter`] methods.
+#[derive(Debug)]
+pub enum PackError {
+    /// An I/O error occurred.
+    Io(io::Error),
+    /// The data is not a pack file, or has an unsupported version.
+    InvalidHeader,
+    /// The pack index is malformed.
+    /// Contains the decoding error, if any (e.g. none if the index does not end after the last entry).
+    InvalidIndex(Option<DecodeError>),
+    /// A pack entry is out of order or duplicated, or its data is out of bounds.
+    /// Contains the path of the entry.
+    InvalidEntry(FilePathBuf),
+    /// A path was added to the [`PackWriter`] more than once.
+    /// Contains the path.
+    DuplicatePath(FilePathBuf),
+    /// A pack entry path is an ancestor or a descendant of another entry's path (e.g. `a` and `a/b`),
+    /// i.e. the entries cannot be unpacked as files.
+    /// Contains the path of the (later added / read) entry.
+    ConflictingPath(FilePathBuf),
+}
+
+impl std::error::Error for PackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PackError::Io(err) => Some(err),
+            PackError::InvalidIndex(Some(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use PackError::*;
+
+        match self {
+            Io(err) => write!(f, "pack I/O error: {err}"),
+            InvalidHeader => "not a pack file or unsupported pack file version".fmt(f),
+            InvalidIndex(Some(err)) => write!(f, "invalid pack index: {err}"),
+            InvalidIndex(None) => "invalid pack index".fmt(f),
+            InvalidEntry(path) => write!(f, "invalid pack entry \"{path}\""),
+            DuplicatePath(path) => write!(f, "duplicate pack entry \"{path}\""),
+            ConflictingPath(path) => write!(
+                f,
+                "pack entry \"{path}\" conflicts with an ancestor or a descendant entry"
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for PackError {
+    fn from(err: io::Error) -> Self {
+        PackError::Io(err)
+    }
+}
+
+impl From<DecodeError> for PackError {
+    fn from(err: DecodeError) -> Self {
+        PackError::InvalidIndex(Some(err))
+    }
+}
+
+/// An entry of the pack file index.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PackEntry {
+    /// Path of the entry, validated when the pack file was written and when it is read.
+    pub path: FilePathBuf,
+    /// Offset in bytes of the entry's data from the start of the pack file.
+    pub offset: u64,
+    /// Size in bytes of the entry's data.
+    pub size: u64,
+    /// Optional content hash of the entry's data (e.g. XXH64), provided when the pack file was written.
+    pub hash: Option<u64>,
+}
+
+/// Streaming writer of the pack files.
+///
+/// Pack file layout:
+/// - header: [`PACK_MAGIC`], little-endian `u32` [`PACK_VERSION`];
+/// - data section: data of all entries, in order they were added;
+/// - index: entries sorted by path (componentwise), each entry is the [`encoded`](AsFilePath::encode) path,
+///   little-endian `u64` data offset and size, `u8` flags and, if flagged, the little-endian `u64` content hash;
+/// - footer: little-endian `u64` index offset, little-endian `u32` entry count, [`PACK_MAGIC`].
+///
+/// Entry paths are [`FilePath`]'s, i.e. are always relative and never contain parent directory components,
+/// so [`unpacking`](PackReader::unpack) a pack file never writes outside of the target directory.
+/// No entry path is an ancestor of another (e.g. `a` and `a/b`), so all entries can be unpacked as files.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{FilePath, PackReader, PackWriter};
+///
+/// let mut writer = PackWriter::new(Vec::new()).unwrap();
+/// writer.add(FilePath::new("textures/barrel.png").unwrap(), b"png", None).unwrap();
+/// writer.add(FilePath::new("meshes/barrel.fbx").unwrap(), b"fbx", Some(7)).unwrap();
+/// let bytes = writer.finish().unwrap();
+///
+/// let pack = PackReader::from_slice(&bytes).unwrap();
+/// assert_eq!(pack.data(FilePath::new("textures/barrel.png").unwrap()), Some(&b"png"[..]));
+/// assert_eq!(pack.get(FilePath::new("meshes/barrel.fbx").unwrap()).unwrap().hash, Some(7));
+/// ```
+pub struct PackWriter<W: Write> {
+    writer: W,
+    /// Offset of the end of the data section.
+    offset: u64,
+    entries: BTreeMap<FilePathBuf, (u64, u64, Option<u64>)>,
+}
+
+impl<W: Write> PackWriter<W> {
+    /// Creates a [`PackWriter`] and writes the pack file header to the `writer`.
+    pub fn new(mut writer: W) -> Result<Self, PackError> {
+        writer.write_all(&PACK_MAGIC)?;
+        writer.write_all(&PACK_VERSION.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            offset: HEADER_SIZE,
+            entries: BTreeMap::new(),
+        })
+    }
+
+    /// Writes the entry at `path` with the `data` and an optional content `hash`.
+    ///
+    /// Returns an [`error`](PackError) if the `path` was already added, or on I/O errors.
+    pub fn add(
+        &mut self,
+        path: &FilePath,
+        data: &[u8],
+        hash: Option<u64>,
+    ) -> Result<(), PackError> {
+        self.add_reader(path, &mut &data[..], hash).map(|_| ())
+    }
+
+    /// Writes the entry at `path` with the data streamed from the `reader` and an optional content `hash`.
+    ///
+    /// Returns the size in bytes of the entry's data.
+    ///
+    /// Returns an [`error`](PackError) if the `path` was already added, if it is an ancestor or a descendant
+    /// of an already added path, or on I/O errors.
+    pub fn add_reader<R: Read + ?Sized>(
+        &mut self,
+        path: &FilePath,
+        reader: &mut R,
+        hash: Option<u64>,
+    ) -> Result<u64, PackError> {
+        if self.entries.contains_key(path) {
+            return Err(PackError::DuplicatePath(path.to_owned()));
+        }
+
+        // Entries are sorted componentwise, so (absent other conflicts) an ancestor entry immediately precedes the `path`,
+        // and a descendant entry immediately follows it.
+        let has_ancestor = self
+            .entries
+            .range::<FilePath, _>((Bound::Unbounded, Bound::Excluded(path)))
+            .next_back()
+            .is_some_and(|(prev, _)| path.starts_with(prev));
+        let has_descendant = self
+            .entries
+            .range::<FilePath, _>((Bound::Included(path), Bound::Unbounded))
+            .next()
+            .is_some_and(|(next, _)| next.starts_with(path));
+        if has_ancestor || has_descendant {
+            return Err(PackError::ConflictingPath(path.to_owned()));
+        }
+
+        let size = io::copy(reader, &mut self.writer)?;
+        self.entries
+            .insert(path.to_owned(), (self.offset, size, hash));
+        self.offset += size;
+
+        Ok(size)
+    }
+
+    /// Writes the index and the footer of the pack file, returning the `writer`.
+    pub fn finish(mut self) -> Result<W, PackError> {
+        let mut index = Vec::new();
+
+        for (path, (offset, size, hash)) in self.entries.iter() {
+            path.encode(&mut index);
+            index.extend_from_slice(&offset.to_le_bytes());
+            index.extend_from_slice(&size.to_le_bytes());
+            if let Some(hash) = hash {
+                index.push(HAS_HASH);
+                index.extend_from_slice(&hash.to_le_bytes());
+            } else {
+                index.push(0);
+            }
+        }
+
+        let len = u32::try_from(self.entries.len())
+            .map_err(|_| io::Error::other("too many pack entries"))?;
+
+        self.writer.write_all(&index)?;
+        self.writer.write_all(&self.offset.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&PACK_MAGIC)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Reader of the pack files written by the [`PackWriter`], from any `Read + Seek` source.
+///
+/// Reads and validates the whole index on creation; reads the entries' data on demand.
+///
+/// Use [`from_slice`](PackReader::from_slice) to read the pack file from memory (e.g. a memory-mapped file)
+/// without copying the entries' data.
+pub struct PackReader<R> {
+    reader: R,
+    /// Sorted by path.
+    entries: Vec<PackEntry>,
+}
+
+impl<'a> PackReader<Cursor<&'a [u8]>> {
+    /// Tries to read the pack file index from the `bytes`.
+    ///
+    /// Returns an [`error`](PackError) if the `bytes` do not contain a valid pack file.
+    pub fn from_slice(bytes: &'a [u8]) -> Result<Self, PackError> {
+        Self::new(Cursor::new(bytes))
+    }
+
+    /// Returns the data of the entry at `path` (without copying it), if it exists.
+    pub fn data(&self, path: &FilePath) -> Option<&'a [u8]> {
+        self.get(path).map(|entry| self.entry_data(entry))
+    }
+
+    /// Returns the data of the `entry` (without copying it).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `entry` was not returned by this [`PackReader`].
+    pub fn entry_data(&self, entry: &PackEntry) -> &'a [u8] {
+        let bytes: &'a [u8] = self.reader.get_ref();
+        // Validated on creation.
+        &bytes[entry.offset as usize..(entry.offset + entry.size) as usize]
+    }
+}
+
+impl<R: Read + Seek> PackReader<R> {
+    /// Tries to read the pack file index from the `reader`.
+    ///
+    /// Returns an [`error`](PackError) if the `reader` does not contain a valid pack file, or on I/O errors.
+    pub fn new(mut reader: R) -> Result<Self, PackError> {
+        let mut header = [0; HEADER_SIZE as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        read_exact(&mut reader, &mut header)?;
+        if header[..4] != PACK_MAGIC || header[4..] != PACK_VERSION.to_le_bytes() {
+            return Err(PackError::InvalidHeader);
+        }
+
+        let stream_len = reader.seek(SeekFrom::End(0))?;
+        if stream_len < HEADER_SIZE + FOOTER_SIZE {
+            return Err(PackError::InvalidHeader);
+        }
+        let footer_offset = reader.seek(SeekFrom::Start(stream_len - FOOTER_SIZE))?;
+        let mut footer = [0; FOOTER_SIZE as usize];
+        read_exact(&mut reader, &mut footer)?;
+        let (index_offset, footer) = footer.split_at(8);
+        let (len, magic) = footer.split_at(4);
+        let index_offset = u64::from_le_bytes(index_offset.try_into().unwrap());
+        let len = u32::from_le_bytes(len.try_into().unwrap());
+        if magic != PACK_MAGIC || !(HEADER_SIZE..=footer_offset).contains(&index_offset) {
+            return Err(PackError::InvalidHeader);
+        }
+
+        let mut index = vec![0; (footer_offset - index_offset) as usize];
+        reader.seek(SeekFrom::Start(index_offset))?;
+        read_exact(&mut reader, &mut index)?;
+
+        Ok(Self {
+            reader,
+            entries: parse_index(&index, len, index_offset)?,
+        })
+    }
+
+    /// Reads the data of the entry at `path`, if it exists.
+    pub fn read(&mut self, path: &FilePath) -> Result<Option<Vec<u8>>, PackError> {
+        let Some(idx) = self.position(path) else {
+            return Ok(None);
+        };
+        let PackEntry { offset, size, .. } = self.entries[idx];
+
+        let mut data = vec![0; size as usize];
+        self.reader.seek(SeekFrom::Start(offset))?;
+        read_exact(&mut self.reader, &mut data)?;
+
+        Ok(Some(data))
+    }
+
+    /// Extracts all entries of the pack file to the `dir`, creating the directories as necessary.
+    ///
+    /// Entry paths are always relative and never contain parent directory components,
+    /// so this never writes outside of the `dir`.
+    pub fn unpack<P: AsRef<std::path::Path>>(&mut self, dir: P) -> Result<(), PackError> {
+        let dir = dir.as_ref();
+
+        for idx in 0..self.entries.len() {
+            let PackEntry {
+                ref path,
+                offset,
+                size,
+                ..
+            } = self.entries[idx];
+            let path = dir.join(path.as_path());
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            self.reader.seek(SeekFrom::Start(offset))?;
+            let mut file = fs::File::create(path)?;
+            let copied = io::copy(&mut (&mut self.reader).take(size), &mut file)?;
+            if copied != size {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> PackReader<R> {
+    /// Returns the number of entries in the pack file.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns all entries of the pack file, sorted by path (componentwise).
+    pub fn entries(&self) -> &[PackEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry at `path`, if it exists.
+    pub fn get(&self, path: &FilePath) -> Option<&PackEntry> {
+        self.position(path).map(|idx| &self.entries[idx])
+    }
+
+    /// Returns all entries located (maybe indirectly) in the `dir`, sorted by path (componentwise).
+    pub fn entries_in(&self, dir: &FilePath) -> &[PackEntry] {
+        // All paths starting with the `dir` are ordered after it and before any other following path.
+        let start = self
+            .entries
+            .partition_point(|entry| entry.path.as_file_path() <= dir);
+        let len = self.entries[start..].partition_point(|entry| entry.path.starts_with(dir));
+        &self.entries[start..start + len]
+    }
+
+    /// Returns all (unique) directory paths which contain the pack file entries, sorted componentwise.
+    pub fn dirs(&self) -> Vec<&FilePath> {
+        let mut dirs = BTreeSet::new();
+
+        for entry in self.entries.iter() {
+            let mut path = entry.path.as_file_path();
+            while let Some(FilePathAndName { file_path, .. }) = split_canonical(path) {
+                if !dirs.insert(file_path) {
+                    break;
+                }
+                path = file_path;
+            }
+        }
+
+        dirs.into_iter().collect()
+    }
+
+    /// Consumes the [`PackReader`], returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn position(&self, path: &FilePath) -> Option<usize> {
+        self.entries
+            .binary_search_by(|entry| entry.path.as_file_path().cmp(path))
+            .ok()
+    }
+}
+
+/// Splits the canonical `path` into the parent directory path and file name parts.
+fn split_canonical(path: &FilePath) -> Option<FilePathAndName<'_>> {
+    let (file_path, file_name) = path.as_str().rsplit_once(SEPARATOR_CHAR)?;
+    // Canonical paths are split into valid canonical paths / components.
+    Some(unsafe {
+        FilePathAndName {
+            file_path: FilePath::new_unchecked(file_path),
+            file_name: FileName::new_unchecked(file_name),
+        }
+    })
+}
+
+fn parse_index(mut bytes: &[u8], len: u32, data_end: u64) -> Result<Vec<PackEntry>, PackError> {
+    let mut entries: Vec<PackEntry> = Vec::new();
+
+    for _ in 0..len {
+        let (path, rest) = FilePath::decode(bytes)?;
+        let (offset, rest) = split_u64(rest)?;
+        let (size, rest) = split_u64(rest)?;
+        let (&flags, rest) = rest.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        let (hash, rest) = if flags & HAS_HASH != 0 {
+            split_u64(rest).map(|(hash, rest)| (Some(hash), rest))?
+        } else {
+            (None, rest)
+        };
+        bytes = rest;
+
+        let in_bounds =
+            offset >= HEADER_SIZE && offset.checked_add(size).is_some_and(|end| end <= data_end);
+        let sorted = entries
+            .last()
+            .is_none_or(|prev| prev.path.as_file_path() < path);
+        if !in_bounds || !sorted {
+            return Err(PackError::InvalidEntry(path.to_owned()));
+        }
+        // Sorted componentwise, so (absent other conflicts) an ancestor entry immediately precedes the `path`.
+        if entries
+            .last()
+            .is_some_and(|prev| path.starts_with(&prev.path))
+        {
+            return Err(PackError::ConflictingPath(path.to_owned()));
+        }
+
+        entries.push(PackEntry {
+            path: path.to_owned(),
+            offset,
+            size,
+            hash,
+        });
+    }
+
+    if bytes.is_empty() {
+        Ok(entries)
+    } else {
+        Err(PackError::InvalidIndex(None))
+    }
+}
+
+fn split_u64(bytes: &[u8]) -> Result<(u64, &[u8]), DecodeError> {
+    let (value, rest) = bytes
+        .split_first_chunk::<8>()
+        .ok_or(DecodeError::UnexpectedEnd)?;
+    Ok((u64::from_le_bytes(*value), rest))
+}
+
+/// Treats unexpected ends of the pack file as invalid pack files.
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), PackError> {
+    reader.read_exact(buf).map_err(|err| {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            PackError::InvalidHeader
+        } else {
+            PackError::Io(err)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> &FilePath {
+        FilePath::new(path).unwrap()
+    }
+
+    fn pack() -> Vec<u8> {
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer
+            .add(path("textures/props/barrel.png"), b"barrel", Some(1))
+            .unwrap();
+        writer.add(path("readme.txt"), b"", None).unwrap();
+        assert_eq!(
+            writer
+                .add_reader(path("textures/crate.png"), &mut &b"crate"[..], None)
+                .unwrap(),
+            5
+        );
+        writer
+            .add(path("textures2/crate.png"), b"crate2", None)
+            .unwrap();
+        assert!(matches!(
+            writer.add(path("textures//crate.png"), b"", None).err().unwrap(),
+            PackError::DuplicatePath(path) if path.as_str() == "textures/crate.png"
+        ));
+        assert!(matches!(
+            writer.add(path("textures/props"), b"", None).err().unwrap(),
+            PackError::ConflictingPath(path) if path.as_str() == "textures/props"
+        ));
+        assert!(matches!(
+            writer.add(path("readme.txt/a"), b"", None).err().unwrap(),
+            PackError::ConflictingPath(path) if path.as_str() == "readme.txt/a"
+        ));
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn pack_reader() {
+        let bytes = pack();
+
+        let pack = PackReader::from_slice(&bytes).unwrap();
+        assert_eq!(pack.len(), 4);
+        assert_eq!(
+            pack.entries()
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "readme.txt",
+                "textures/crate.png",
+                "textures/props/barrel.png",
+                "textures2/crate.png"
+            ]
+        );
+        assert_eq!(
+            pack.data(path("textures/props/barrel.png")),
+            Some(&b"barrel"[..])
+        );
+        assert_eq!(pack.data(path("readme.txt")), Some(&b""[..]));
+        assert_eq!(pack.data(path("textures")), None);
+        assert_eq!(
+            pack.get(path("textures/props/barrel.png")).unwrap().hash,
+            Some(1)
+        );
+        assert_eq!(
+            pack.entries_in(path("textures"))
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<_>>(),
+            ["textures/crate.png", "textures/props/barrel.png"]
+        );
+        assert!(pack.entries_in(path("textures/crate.png")).is_empty());
+        assert!(pack.entries_in(path("meshes")).is_empty());
+        assert_eq!(
+            pack.dirs(),
+            [path("textures"), path("textures/props"), path("textures2")]
+        );
+
+        let mut pack = PackReader::new(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(
+            pack.read(path("textures/crate.png")).unwrap().unwrap(),
+            b"crate"
+        );
+        assert!(pack.read(path("textures/missing.png")).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = pack();
+
+        assert!(matches!(
+            PackReader::from_slice(&bytes[..bytes.len() - 1])
+                .err()
+                .unwrap(),
+            PackError::InvalidHeader
+        ));
+        assert!(matches!(
+            PackReader::from_slice(&bytes[1..]).err().unwrap(),
+            PackError::InvalidHeader
+        ));
+        assert!(matches!(
+            PackReader::from_slice(&[]).err().unwrap(),
+            PackError::InvalidHeader
+        ));
+
+        // Data out of bounds.
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add(path("a"), b"a", None).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        // Size of the entry "a".
+        let size = 8 + 1 + 2 + 1 + 8;
+        bytes[size] = 2;
+        assert!(matches!(
+            PackReader::from_slice(&bytes).err().unwrap(),
+            PackError::InvalidEntry(path) if path.as_str() == "a"
+        ));
+
+        // Invalid path.
+        bytes[size] = 1;
+        bytes[8 + 1 + 2] = b'.';
+        assert!(matches!(
+            PackReader::from_slice(&bytes).err().unwrap(),
+            PackError::InvalidIndex(Some(DecodeError::InvalidPath(_)))
+        ));
+
+        // Header only, or a truncated footer.
+        let header_len = HEADER_SIZE as usize;
+        for len in header_len..header_len + FOOTER_SIZE as usize {
+            assert!(matches!(
+                PackReader::new(Cursor::new(&bytes[..len])).err().unwrap(),
+                PackError::InvalidHeader
+            ));
+        }
+
+        // Trailing index bytes: the footer entry count is one less than the number of entries.
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add(path("a"), b"a", None).unwrap();
+        writer.add(path("b"), b"b", None).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        let len_offset = bytes.len() - 8;
+        bytes[len_offset] = 1;
+        assert!(matches!(
+            PackReader::from_slice(&bytes).err().unwrap(),
+            PackError::InvalidIndex(None)
+        ));
+
+        // Conflicting entries: "b/b" is patched to "a/b", a descendant of "a".
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        writer.add(path("a"), b"x", None).unwrap();
+        writer.add(path("b/b"), b"x", None).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        let path_offset = bytes.windows(3).position(|w| w == b"b/b").unwrap();
+        bytes[path_offset] = b'a';
+        assert!(matches!(
+            PackReader::from_slice(&bytes).err().unwrap(),
+            PackError::ConflictingPath(path) if path.as_str() == "a/b"
+        ));
+    }
+
+    #[test]
+    fn unpack() {
+        let dir = std::env::temp_dir().join(format!("minifilepath_unpack_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let bytes = pack();
+        PackReader::from_slice(&bytes)
+            .unwrap()
+            .unpack(&dir)
+            .unwrap();
+        assert_eq!(
+            fs::read(dir.join("textures/props/barrel.png")).unwrap(),
+            b"barrel"
+        );
+        assert_eq!(fs::read(dir.join("readme.txt")).unwrap(), b"");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,18 +1,19 @@
 use {
     crate::*,
-    ministr::{NonEmptyStr, NonEmptyString},
-    std::{
+    alloc::{rc::Rc, sync::Arc},
+    core::{
         borrow::Borrow,
+        cmp::{Ord, Ordering, PartialOrd},
         fmt::{Display, Formatter},
         hash::{Hash, Hasher},
-        iter::DoubleEndedIterator,
         ops::Deref,
-        path::{Path, PathBuf},
+        str::FromStr,
     },
+    ministr::{NonEmptyStr, NonEmptyString},
 };
 
 /// Non-empty, relative, case agnostic UTF-8 file system path.
-/// Every [`FilePathBuf`] is a valid [`PathBuf`], but not vice-versa.
+/// Every [`FilePathBuf`] is a valid [`PathBuf`](std::path::PathBuf), but not vice-versa.
 ///
 /// The string representation contains nothing but normal path components.
 /// Always uses forward slashes as path component separators.
@@ -20,7 +21,8 @@ use {
 /// NOTE: [`FilePath`]'s are considered equal if they produce the same [`components`](#method.components),
 /// or, equivalently, if the underlying strings are equal.
 ///
-/// Hashed componentwise, not as the string representation.
+/// Hashed and ordered componentwise, not as the string representation
+/// (i.e. `"a/b"` is ordered before `"a-b"`), consistent with [`FilePath`].
 ///
 /// E.g.: "foo/βαρ/Baz BoB.txt", "textures/props/barrels/red_barrel.png".
 /// But not "/foo/bar/", or "C:\Bill\Amy.cfg", or "../meshes/props/barrels/red_barrel.fbx".
@@ -30,14 +32,14 @@ use {
 pub struct FilePathBuf(pub(crate) NonEmptyString);
 
 impl FilePathBuf {
-    /// Tries to create a [`FilePathBuf`] directly from a [`path`](Path).
+    /// Tries to create a [`FilePathBuf`] directly from a [`path`](RawPath).
     ///
-    /// Returns an [`error`](FilePathError) if the [`path`](Path) is not a valid [`FilePathBuf`].
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`FilePathBuf`].
     ///
     /// You can also build a [`FilePathBuf`] using a [`FilePathBuilder`].
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, FilePathError> {
+    pub fn new<P: AsRef<RawPath>>(path: P) -> Result<Self, FilePathError> {
         let path = path.as_ref();
-        let mut builder = FilePathBuilder::with_capacity(path.as_os_str().len());
+        let mut builder = FilePathBuilder::with_capacity(raw_path_len(path));
         builder.push(path)?;
         builder.build().ok_or(FilePathError::EmptyPath)
     }
@@ -62,7 +64,7 @@ impl FilePathBuf {
     /// Returns the length in bytes of the [`FilePathBuf`]. Always > 0.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
-        self.0.len()
+        AsFilePath::len(self)
     }
 
     /// Converts the [`FilePathBuf`] back to a [`FilePathBuilder`], without clearing it,
@@ -71,8 +73,9 @@ impl FilePathBuf {
         FilePathBuilder::from(self.0.into_inner())
     }
 
-    pub fn into_path(self) -> PathBuf {
-        PathBuf::from(self.0.into_inner())
+    #[cfg(feature = "std")]
+    pub fn into_path(self) -> std::path::PathBuf {
+        std::path::PathBuf::from(self.0.into_inner())
     }
 
     pub fn into_ne_string(self) -> NonEmptyString {
@@ -83,31 +86,39 @@ impl FilePathBuf {
         self.0.into_inner()
     }
 
+    /// Converts the [`FilePathBuf`] to a boxed [`FilePath`] (in canonical string representation),
+    /// without reallocating if the [`FilePathBuf`] has no excess capacity.
+    pub fn into_boxed_file_path(self) -> Box<FilePath> {
+        let path = Box::into_raw(self.into_string().into_boxed_str());
+        // `FilePath` is a `#[repr(transparent)]` wrapper around a (non-empty) `str`.
+        unsafe { Box::from_raw(path as *mut FilePath) }
+    }
+
     pub fn as_file_path(&self) -> &FilePath {
         // It is safe to directly convert a `NonEmptyStr` with a valid path to a `FilePath`.
         unsafe { FilePath::from_str(self.0.as_ne_str()) }
     }
 
-    pub fn as_path(&self) -> &Path {
-        Path::new(self.0.as_str())
+    #[cfg(feature = "std")]
+    pub fn as_path(&self) -> &std::path::Path {
+        AsFilePath::as_path(self)
     }
 
     pub fn as_ne_str(&self) -> &NonEmptyStr {
-        self.0.as_ne_str()
+        AsFilePath::as_ne_str(self)
     }
 
     pub fn as_str(&self) -> &str {
-        self.0.as_str()
+        AsFilePath::as_str(self)
     }
 
-    /// Returns an [`iterator`](FilePathBufIter) over the (non-empty, UTF-8 string) components of the [`FilePathBuf`], root to leaf.
+    /// Returns an [`iterator`](FilePathBufIter) over the [`file name`](FileName) components of the [`FilePathBuf`], root to leaf.
     ///
     /// NOTE: file name, with extension or not, is a single component.
     ///
     /// NOTE: can be reversed via `rev()` to iterate leaf to root.
     pub fn components(&self) -> FilePathBufIter<'_> {
-        // Unlike `FilePath`, we may use the simpler iterator because of the `FilePathBuf`'s canonical string representation.
-        FilePathBufIter::new(self.as_file_path())
+        AsFilePath::components(self)
     }
 
     /// Returns the file name portion of the [`FilePathBuf`] (i.e. the last/leaf component).
@@ -120,12 +131,8 @@ impl FilePathBuf {
     /// assert_eq!(FilePathBuf::new("foo/.txt").unwrap().file_name(), nestr!(".txt"));
     /// assert_eq!(FilePathBuf::new("foo/bar/baz").unwrap().file_name(), nestr!("baz"));
     /// ```
-    pub fn file_name(&self) -> FilePathComponent<'_> {
-        unsafe {
-            self.components()
-                .next_back()
-                .unwrap_unchecked_dbg_msg("empty `FilePathBuf`'s are invalid")
-        }
+    pub fn file_name(&self) -> &FileName {
+        AsFilePath::file_name(self)
     }
 
     /// Returns the file stem portion of the [`FilePathBuf`] (i.e. the non-extension part of the last/leaf component).
@@ -140,11 +147,8 @@ impl FilePathBuf {
     /// assert_eq!(FilePathBuf::new("foo/.txt").unwrap().file_stem(), None);
     /// assert_eq!(FilePathBuf::new("foo/bar/baz").unwrap().file_stem(), Some(nestr!("baz")));
     /// ```
-    pub fn file_stem(&self) -> Option<FilePathComponent<'_>> {
-        let file_name = self.file_name();
-        file_stem_and_extension(file_name)
-            .map(|file_stem_and_extension| file_stem_and_extension.file_stem)
-            .unwrap_or(Some(file_name))
+    pub fn file_stem(&self) -> Option<&NonEmptyStr> {
+        AsFilePath::file_stem(self)
     }
 
     /// Returns the extension portion of the [`FilePathBuf`] (i.e. the extension part of the last/leaf component).
@@ -159,9 +163,8 @@ impl FilePathBuf {
     /// assert_eq!(FilePathBuf::new("foo/.txt").unwrap().extension(), Some(nestr!("txt")));
     /// assert_eq!(FilePathBuf::new("foo/bar/baz").unwrap().extension(), None);
     /// ```
-    pub fn extension(&self) -> Option<FilePathComponent<'_>> {
-        file_stem_and_extension(self.file_name())
-            .map(|file_stem_and_extension| file_stem_and_extension.extension)
+    pub fn extension(&self) -> Option<&NonEmptyStr> {
+        AsFilePath::extension(self)
     }
 
     /// Used to debug validate the `path` in `new_unchecked()`.
@@ -192,20 +195,228 @@ impl Borrow<FilePath> for FilePathBuf {
     }
 }
 
+impl AsRef<str> for FilePathBuf {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for FilePathBuf {
+    fn as_ref(&self) -> &std::path::Path {
+        self.as_path()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::ffi::OsStr> for FilePathBuf {
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.as_path().as_os_str()
+    }
+}
+
+impl FromStr for FilePathBuf {
+    type Err = FilePathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<&str> for FilePathBuf {
+    type Error = FilePathError;
+
+    fn try_from(path: &str) -> Result<Self, Self::Error> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<String> for FilePathBuf {
+    type Error = FilePathError;
+
+    /// Reuses the `path`'s allocation if it is already in the canonical string representation.
+    fn try_from(path: String) -> Result<Self, Self::Error> {
+        let file_path = FilePath::new(path.as_str())?;
+
+        if file_path.is_canonical() {
+            Ok(Self(unsafe { NonEmptyString::new_unchecked(path) }))
+        } else {
+            Ok(file_path.to_owned())
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<std::path::PathBuf> for FilePathBuf {
+    type Error = FilePathError;
+
+    /// Reuses the `path`'s allocation if it is already in the canonical string representation.
+    fn try_from(path: std::path::PathBuf) -> Result<Self, Self::Error> {
+        match path.into_os_string().into_string() {
+            Ok(path) => Self::try_from(path),
+            // Returns the `InvalidUTF8` error.
+            Err(path) => Self::new(path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FilePathBuf> for std::path::PathBuf {
+    fn from(path: FilePathBuf) -> Self {
+        path.into_path()
+    }
+}
+
+impl From<FilePathBuf> for String {
+    fn from(path: FilePathBuf) -> Self {
+        path.into_string()
+    }
+}
+
+impl PartialEq<FilePath> for FilePathBuf {
+    fn eq(&self, other: &FilePath) -> bool {
+        self.as_file_path() == other
+    }
+}
+
+impl PartialEq<&FilePath> for FilePathBuf {
+    fn eq(&self, other: &&FilePath) -> bool {
+        self.as_file_path() == *other
+    }
+}
+
+/// Compared componentwise, same as [`FilePath`]'s; never equal to an invalid path string.
+///
+/// NOTE: validates the path string on each comparison.
+impl PartialEq<str> for FilePathBuf {
+    fn eq(&self, other: &str) -> bool {
+        self.as_file_path() == other
+    }
+}
+
+/// Same as `PartialEq<str>`; validates the path string on each comparison.
+impl PartialEq<&str> for FilePathBuf {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_file_path() == *other
+    }
+}
+
+/// Same as `PartialEq<str>`; validates the path string on each comparison.
+impl PartialEq<FilePathBuf> for str {
+    fn eq(&self, other: &FilePathBuf) -> bool {
+        other == self
+    }
+}
+
+/// Same as `PartialEq<str>`; validates the path string on each comparison.
+impl PartialEq<FilePathBuf> for &str {
+    fn eq(&self, other: &FilePathBuf) -> bool {
+        other == *self
+    }
+}
+
+/// Compared componentwise, same as [`FilePath`]'s; never equal to an invalid path.
+///
+/// NOTE: validates the path on each comparison.
+#[cfg(feature = "std")]
+impl PartialEq<std::path::Path> for FilePathBuf {
+    fn eq(&self, other: &std::path::Path) -> bool {
+        self.as_file_path() == other
+    }
+}
+
+/// Same as `PartialEq<Path>`; validates the path on each comparison.
+#[cfg(feature = "std")]
+impl PartialEq<&std::path::Path> for FilePathBuf {
+    fn eq(&self, other: &&std::path::Path) -> bool {
+        self.as_file_path() == *other
+    }
+}
+
+/// Same as `PartialEq<Path>`; validates the path on each comparison.
+#[cfg(feature = "std")]
+impl PartialEq<FilePathBuf> for std::path::Path {
+    fn eq(&self, other: &FilePathBuf) -> bool {
+        other == self
+    }
+}
+
+/// Same as `PartialEq<Path>`; validates the path on each comparison.
+#[cfg(feature = "std")]
+impl PartialEq<FilePathBuf> for &std::path::Path {
+    fn eq(&self, other: &FilePathBuf) -> bool {
+        other == *self
+    }
+}
+
 impl From<&FilePath> for FilePathBuf {
     fn from(path: &FilePath) -> Self {
         path.to_owned()
     }
 }
 
+impl From<FilePathBuf> for Box<FilePath> {
+    fn from(path: FilePathBuf) -> Self {
+        path.into_boxed_file_path()
+    }
+}
+
+impl From<Box<FilePath>> for FilePathBuf {
+    fn from(path: Box<FilePath>) -> Self {
+        // Boxed `FilePath`'s always use the canonical string representation.
+        let path: Box<str> = unsafe { Box::from_raw(Box::into_raw(path) as *mut str) };
+        Self(unsafe { NonEmptyString::new_unchecked(path.into_string()) })
+    }
+}
+
+impl Clone for Box<FilePath> {
+    fn clone(&self) -> Self {
+        // Boxed `FilePath`'s always use the canonical string representation.
+        let path: Box<str> = self.as_str().into();
+        unsafe { Box::from_raw(Box::into_raw(path) as *mut FilePath) }
+    }
+}
+
+impl From<FilePathBuf> for Arc<FilePath> {
+    fn from(path: FilePathBuf) -> Self {
+        let path: Arc<str> = Arc::from(path.into_string());
+        // `FilePath` is a `#[repr(transparent)]` wrapper around a (non-empty) `str`.
+        unsafe { Arc::from_raw(Arc::into_raw(path) as *const FilePath) }
+    }
+}
+
+impl From<FilePathBuf> for Rc<FilePath> {
+    fn from(path: FilePathBuf) -> Self {
+        let path: Rc<str> = Rc::from(path.into_string());
+        // `FilePath` is a `#[repr(transparent)]` wrapper around a (non-empty) `str`.
+        unsafe { Rc::from_raw(Rc::into_raw(path) as *const FilePath) }
+    }
+}
+
 impl Hash for FilePathBuf {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.as_file_path().hash(state)
     }
 }
 
+impl PartialOrd<Self> for FilePathBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FilePathBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Same as `FilePath`, but uses the simpler iterator.
+        Iterator::cmp(
+            self.components().map(FileName::as_str),
+            other.components().map(FileName::as_str),
+        )
+    }
+}
+
 impl Display for FilePath {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -237,7 +448,7 @@ mod tests {
     fn CurrentDirectory() {
         assert_eq!(
             FilePathBuf::new("./foo\\baz").err().unwrap(),
-            FilePathError::CurrentDirectory(PathBuf::new())
+            FilePathError::CurrentDirectory(ErrorPath::new())
         );
         // But this works:
         let foobaz = FilePathBuf::new("foo\\.\\baz").unwrap();
@@ -249,11 +460,11 @@ mod tests {
     fn ParentDirectory() {
         assert_eq!(
             FilePathBuf::new("..\\foo").err().unwrap(),
-            FilePathError::ParentDirectory(PathBuf::new())
+            FilePathError::ParentDirectory(ErrorPath::new())
         );
         assert_eq!(
             FilePathBuf::new("foo/..").err().unwrap(),
-            FilePathError::ParentDirectory(PathBuf::from("foo"))
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
         );
     }
 
@@ -277,7 +488,7 @@ mod tests {
 
         assert_eq!(
             FilePathBuf::new(invalid_component).err().unwrap(),
-            FilePathError::ComponentTooLong((PathBuf::from(invalid_component), invalid_len))
+            FilePathError::ComponentTooLong((ErrorPath::from(invalid_component), invalid_len))
         );
 
         let valid_component = vec![b'a'; MAX_COMPONENT_LEN];
@@ -290,15 +501,15 @@ mod tests {
     fn InvalidCharacter() {
         assert_eq!(
             FilePathBuf::new("foo\\a?").err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::from("foo\\a?"), '?'))
+            FilePathError::InvalidCharacter((ErrorPath::from("foo\\a?"), '?'))
         );
         assert_eq!(
             FilePathBuf::new("foo/BAR/*").err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::from("foo/BAR/*"), '*'))
+            FilePathError::InvalidCharacter((ErrorPath::from("foo/BAR/*"), '*'))
         );
         assert_eq!(
             FilePathBuf::new("foo/bar<1>").err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::from("foo/bar<1>"), '<'))
+            FilePathError::InvalidCharacter((ErrorPath::from("foo/bar<1>"), '<'))
         );
     }
 
@@ -307,17 +518,17 @@ mod tests {
     fn ComponentEndsWithAPeriod() {
         assert_eq!(
             FilePathBuf::new("foo\\...").err().unwrap(),
-            FilePathError::ComponentEndsWithAPeriod(PathBuf::from("foo\\..."))
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::from("foo\\..."))
         );
         // But this is a parent directory.
         assert_eq!(
             FilePathBuf::new("foo\\..").err().unwrap(),
-            FilePathError::ParentDirectory(PathBuf::from("foo"))
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
         );
         // And this is a current directory.
         assert_eq!(
             FilePathBuf::new("./foo").err().unwrap(),
-            FilePathError::CurrentDirectory(PathBuf::new())
+            FilePathError::CurrentDirectory(ErrorPath::new())
         );
     }
 
@@ -326,7 +537,7 @@ mod tests {
     fn ComponentEndsWithASpace() {
         assert_eq!(
             FilePathBuf::new("foo\\bar.txt ").err().unwrap(),
-            FilePathError::ComponentEndsWithASpace(PathBuf::from("foo\\bar.txt "))
+            FilePathError::ComponentEndsWithASpace(ErrorPath::from("foo\\bar.txt "))
         );
     }
 
@@ -335,11 +546,11 @@ mod tests {
     fn ReservedName() {
         assert_eq!(
             FilePathBuf::new("foo\\NUL").err().unwrap(),
-            FilePathError::ReservedName(PathBuf::from("foo\\NUL"))
+            FilePathError::ReservedName(ErrorPath::from("foo\\NUL"))
         );
         assert_eq!(
             FilePathBuf::new("BAR/com7").err().unwrap(),
-            FilePathError::ReservedName(PathBuf::from("BAR/com7"))
+            FilePathError::ReservedName(ErrorPath::from("BAR/com7"))
         );
     }
 
@@ -357,7 +568,7 @@ mod tests {
 
         assert_eq!(
             FilePathBuf::new(os_str).err().unwrap(),
-            FilePathError::InvalidUTF8(PathBuf::from("foo"))
+            FilePathError::InvalidUTF8(ErrorPath::from("foo"))
         );
     }
 
@@ -428,11 +639,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn boxed_and_shared() {
+        let path = FilePathBuf::new("foo//bar/baz.txt").unwrap();
+
+        let boxed = path.clone().into_boxed_file_path();
+        assert_eq!(boxed.as_str(), "foo/bar/baz.txt");
+        assert_eq!(boxed.file_name().as_str(), "baz.txt");
+        let cloned = boxed.clone();
+        assert_eq!(cloned, boxed);
+        assert_eq!(FilePathBuf::from(cloned), path);
+
+        let arc: Arc<FilePath> = path.clone().into();
+        let arc_clone = Arc::clone(&arc);
+        assert_eq!(
+            std::thread::spawn(move || arc_clone.as_str().to_owned())
+                .join()
+                .unwrap(),
+            "foo/bar/baz.txt"
+        );
+        assert_eq!(*arc, *path);
+
+        let rc: Rc<FilePath> = path.clone().into();
+        assert_eq!(rc.as_str(), "foo/bar/baz.txt");
+        assert_eq!((*rc).to_owned(), path);
+    }
+
     #[test]
     fn equality() {
         let l = FilePathBuf::new("foo/./bar//Baz\\\\BILL\\").unwrap();
         let r = FilePathBuf::new("foo/bar/Baz/BILL").unwrap();
         assert_eq!(l, r);
+        #[cfg(feature = "std")]
         assert_eq!(l.as_path(), r.as_path());
         // Strings and hashes are equal.
         assert_eq!(l.as_str(), r.as_str());
@@ -442,4 +680,77 @@ mod tests {
         r.hash(&mut hr);
         assert_eq!(hl.finish(), hr.finish());
     }
+
+    #[test]
+    fn conversions() {
+        let path: FilePathBuf = "foo//bar/baz.txt".parse().unwrap();
+        assert_eq!(path.as_str(), "foo/bar/baz.txt");
+        assert_eq!(FilePathBuf::try_from("foo/bar/baz.txt").unwrap(), path);
+        assert_eq!(
+            "/foo".parse::<FilePathBuf>().err().unwrap(),
+            FilePathError::RootDirectory
+        );
+
+        // Canonical - reuses the allocation.
+        let string = String::from("foo/bar/baz.txt");
+        let ptr = string.as_ptr();
+        let path_ = FilePathBuf::try_from(string).unwrap();
+        assert_eq!(path_, path);
+        assert_eq!(path_.as_str().as_ptr(), ptr);
+
+        // Non-canonical.
+        assert_eq!(
+            FilePathBuf::try_from(String::from("foo/./bar//baz.txt")).unwrap(),
+            path
+        );
+        assert_eq!(
+            FilePathBuf::try_from(ErrorPath::from("foo/bar/baz.txt/")).unwrap(),
+            path
+        );
+        assert_eq!(
+            FilePathBuf::try_from(String::from("foo/../baz.txt"))
+                .err()
+                .unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
+        );
+
+        let file_path = <&FilePath>::try_from("foo//bar/baz.txt").unwrap();
+        assert_eq!(file_path, path);
+        assert_eq!(path, file_path);
+        assert_eq!(*file_path, path);
+        assert_eq!(path, *file_path);
+
+        assert_eq!(path, "foo/bar/baz.txt");
+        assert_eq!(path, *"foo/./bar/baz.txt");
+        assert_eq!(*file_path, "foo/bar/baz.txt");
+        assert_ne!(path, "foo/bar");
+        assert_ne!(path, "/foo/bar/baz.txt");
+        assert_eq!("foo/bar/baz.txt", path);
+        assert_eq!(*"foo/./bar/baz.txt", path);
+        assert_eq!("foo/bar/baz.txt", *file_path);
+        assert_eq!("foo/bar/baz.txt", file_path);
+        assert_ne!("foo/bar", path);
+        #[cfg(feature = "std")]
+        {
+            let std_path = std::path::Path::new("foo/bar//baz.txt");
+            assert_eq!(path, *std_path);
+            assert_eq!(path, std_path);
+            assert_eq!(*file_path, std_path);
+            assert_eq!(*std_path, path);
+            assert_eq!(std_path, path);
+            assert_eq!(std_path, *file_path);
+            assert_eq!(std_path, file_path);
+            assert_ne!(std::path::Path::new("foo/bar"), path);
+
+            let as_ref: &std::ffi::OsStr = path.as_ref();
+            assert_eq!(as_ref, "foo/bar/baz.txt");
+        }
+        let as_ref: &str = file_path.as_ref();
+        assert_eq!(as_ref, "foo//bar/baz.txt");
+        assert_eq!(
+            ErrorPath::from(path.clone()),
+            ErrorPath::from("foo/bar/baz.txt")
+        );
+        assert_eq!(String::from(path), "foo/bar/baz.txt");
+    }
 }
@@ -0,0 +1,311 @@
+use {
+    crate::*,
+    core::fmt::{Display, Formatter},
+    std::io::{self, BufRead, Write},
+};
+
+/// Separator of the paths in a path list.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Separator {
+    /// Paths are separated by `\n` (or `\r\n`), e.g. `git ls-files` / `find` output.
+    Newline,
+    /// Paths are separated by `\0`, e.g. `git ls-files -z` / `find -print0` output.
+    Nul,
+}
+
+impl Separator {
+    fn as_byte(self) -> u8 {
+        match self {
+            Separator::Newline => b'\n',
+            Separator::Nul => b'\0',
+        }
+    }
+}
+
+/// Returns a [`PathListReader`] which parses the `separator`-separated list of paths read from the `reader`.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{read_path_list, Separator};
+///
+/// let paths = read_path_list("foo/bar.txt\r\n\r\nbaz//qux.txt\n".as_bytes(), Separator::Newline)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(paths[0].as_str(), "foo/bar.txt");
+/// assert_eq!(paths[1].as_str(), "baz/qux.txt");
+/// ```
+pub fn read_path_list<R: BufRead>(reader: R, separator: Separator) -> PathListReader<R> {
+    PathListReader {
+        reader,
+        separator,
+        comments: false,
+        line: 0,
+        done: false,
+        buf: Vec::new(),
+        builder: FilePathBuilder::new(),
+    }
+}
+
+/// Streaming parser of a [`separated`](Separator) list of paths, returned by [`read_path_list`].
+///
+/// Blank (empty or ASCII whitespace-only) lines are skipped,
+/// as well as comment lines starting with `#` if [`enabled`](Self::with_comments).
+/// A trailing `\r` is stripped from each line when using [`Separator::Newline`].
+///
+/// Iterating over the [`PathListReader`] yields [`FilePathBuf`]'s,
+/// while [`read_next`](Self::read_next) reuses the internal buffers and does not allocate per path.
+pub struct PathListReader<R> {
+    reader: R,
+    separator: Separator,
+    comments: bool,
+    line: usize,
+    done: bool,
+    buf: Vec<u8>,
+    builder: FilePathBuilder,
+}
+
+impl<R: BufRead> PathListReader<R> {
+    /// Skip comment lines starting with `#` if `comments` is `true`.
+    /// Comments are not skipped by default.
+    pub fn with_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Returns the (1-based) number of the last read line (or `\0`-separated entry).
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Reads the next path in the list, if any, reusing the internal path buffer.
+    ///
+    /// Returns an [`error`](PathListError) if the path is invalid, in which case the reading may continue with the next path,
+    /// or on I/O errors, after which the reader is exhausted.
+    pub fn read_next(&mut self) -> Option<Result<&FilePath, PathListError>> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            self.buf.clear();
+            match self
+                .reader
+                .read_until(self.separator.as_byte(), &mut self.buf)
+            {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(PathListError::Io(err)));
+                }
+            }
+            self.line += 1;
+
+            let mut line = self.buf.as_slice();
+            line = line
+                .strip_suffix(&[self.separator.as_byte()])
+                .unwrap_or(line);
+            if self.separator == Separator::Newline {
+                line = line.strip_suffix(b"\r").unwrap_or(line);
+            }
+
+            if line.trim_ascii().is_empty() || (self.comments && line.starts_with(b"#")) {
+                continue;
+            }
+
+            let line = match str::from_utf8(line) {
+                Ok(line) => line,
+                Err(_) => {
+                    return Some(Err(PathListError::InvalidPath(
+                        self.line,
+                        FilePathError::InvalidUTF8(ErrorPath::from(
+                            String::from_utf8_lossy(line).as_ref(),
+                        )),
+                    )));
+                }
+            };
+
+            self.builder.clear();
+            return Some(match self.builder.push(line) {
+                Ok(()) => Ok(unsafe {
+                    self.builder.as_file_path().unwrap_unchecked_dbg_msg(
+                        "builder must be non-empty after a successful push",
+                    )
+                }),
+                Err(err) => Err(PathListError::InvalidPath(self.line, err)),
+            });
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for PathListReader<R> {
+    type Item = Result<FilePathBuf, PathListError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next().map(|path| path.map(FilePath::to_owned))
+    }
+}
+
+/// An error returned by [`PathListReader`].
+#[derive(Debug)]
+pub enum PathListError {
+    /// An I/O error occured.
+    Io(io::Error),
+    /// A path in the list is invalid.
+    /// Contains the (1-based) line number (or `\0`-separated entry number) of the path and the validation error.
+    InvalidPath(usize, FilePathError),
+}
+
+impl std::error::Error for PathListError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PathListError::Io(err) => Some(err),
+            PathListError::InvalidPath(_, err) => Some(err),
+        }
+    }
+}
+
+impl Display for PathListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PathListError::Io(err) => write!(f, "I/O error: {err}"),
+            PathListError::InvalidPath(line, err) => {
+                write!(f, "invalid path at line {line}: {err}")
+            }
+        }
+    }
+}
+
+impl From<io::Error> for PathListError {
+    fn from(err: io::Error) -> Self {
+        PathListError::Io(err)
+    }
+}
+
+/// Writes a [`separated`](Separator) list of paths to the wrapped writer, readable by [`read_path_list`].
+///
+/// Paths are written in the canonical string representation, each followed by the separator.
+pub struct PathListWriter<W> {
+    writer: W,
+    separator: Separator,
+}
+
+impl<W: Write> PathListWriter<W> {
+    pub fn new(writer: W, separator: Separator) -> Self {
+        Self { writer, separator }
+    }
+
+    /// Writes the `path`, followed by the separator.
+    pub fn write(&mut self, path: &FilePath) -> io::Result<()> {
+        if path.is_canonical() {
+            self.writer.write_all(path.as_str().as_bytes())?;
+        } else {
+            write!(self.writer, "{}", Canonical(path))?;
+        }
+        self.writer.write_all(&[self.separator.as_byte()])
+    }
+
+    /// Writes all `paths`, each followed by the separator.
+    pub fn write_all<'a, I: IntoIterator<Item = &'a FilePath>>(
+        &mut self,
+        paths: I,
+    ) -> io::Result<()> {
+        paths.into_iter().try_for_each(|path| self.write(path))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(list: &str, separator: Separator, comments: bool) -> Vec<Result<String, usize>> {
+        read_path_list(list.as_bytes(), separator)
+            .with_comments(comments)
+            .map(|path| match path {
+                Ok(path) => Ok(path.into_string()),
+                Err(PathListError::InvalidPath(line, _)) => Err(line),
+                Err(PathListError::Io(err)) => panic!("{err}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn newline() {
+        let list = "foo/bar.txt\r\n\n  \r\n# comment\nbaz//qux.txt\n/invalid\nlast";
+        assert_eq!(
+            read(list, Separator::Newline, true),
+            [
+                Ok("foo/bar.txt".to_owned()),
+                Ok("baz/qux.txt".to_owned()),
+                Err(6),
+                Ok("last".to_owned())
+            ]
+        );
+        assert_eq!(
+            read(list, Separator::Newline, false),
+            [
+                Ok("foo/bar.txt".to_owned()),
+                Ok("# comment".to_owned()),
+                Ok("baz/qux.txt".to_owned()),
+                Err(6),
+                Ok("last".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn nul() {
+        let list = "foo/bar\n.txt\0\0baz\r\0";
+        assert_eq!(read(list, Separator::Nul, false), [Err(1), Err(3)]);
+        assert_eq!(
+            read("foo/bar.txt\0a b/c\0", Separator::Nul, false),
+            [Ok("foo/bar.txt".to_owned()), Ok("a b/c".to_owned())]
+        );
+
+        let mut reader = read_path_list(&b"foo/\xff\0bar"[..], Separator::Nul);
+        assert!(matches!(
+            reader.read_next(),
+            Some(Err(PathListError::InvalidPath(
+                1,
+                FilePathError::InvalidUTF8(_)
+            )))
+        ));
+        assert_eq!(reader.read_next().unwrap().unwrap().as_str(), "bar");
+        assert_eq!(reader.line(), 2);
+        assert!(reader.read_next().is_none());
+    }
+
+    #[test]
+    fn write() {
+        let paths = [
+            FilePath::new("foo//bar.txt").unwrap(),
+            FilePath::new("baz/./qux.txt").unwrap(),
+        ];
+
+        for separator in [Separator::Newline, Separator::Nul] {
+            let mut writer = PathListWriter::new(Vec::new(), separator);
+            writer.write_all(paths).unwrap();
+            let list = writer.into_inner();
+
+            let read = read_path_list(list.as_slice(), separator)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(read, paths);
+        }
+
+        let mut writer = PathListWriter::new(Vec::new(), Separator::Newline);
+        writer.write_all(paths).unwrap();
+        assert_eq!(writer.into_inner(), b"foo/bar.txt\nbaz/qux.txt\n");
+    }
+}
@@ -0,0 +1,295 @@
+use {
+    crate::*,
+    core::fmt::{Display, Formatter},
+    ministr::NonEmptyString,
+    serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, Visitor},
+    },
+};
+
+/// Always serialized in the canonical string representation.
+impl Serialize for FilePath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_canonical() {
+            serializer.serialize_str(self.as_str())
+        } else {
+            serializer.collect_str(&Canonical(self))
+        }
+    }
+}
+
+impl Serialize for FilePathBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Only accepts valid paths in the canonical string representation,
+/// see [`canonicalize`] to accept (and canonicalize) any valid path.
+///
+/// NOTE: the deserializer error only carries the message of the [`FilePathError`], not the error itself;
+/// see [`FilePathBuf::try_deserialize`] for the typed error.
+impl<'de> Deserialize<'de> for FilePathBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_string(FilePathBufVisitor {
+            canonicalize: false,
+        })
+    }
+}
+
+/// Zero-copy, only accepts borrowed valid paths in the canonical string representation.
+///
+/// NOTE: the deserializer error only carries the message of the [`FilePathError`], not the error itself;
+/// see [`FilePathBuf::try_deserialize`] for the typed error.
+impl<'de: 'a, 'a> Deserialize<'de> for &'a FilePath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FilePathVisitor)
+    }
+}
+
+/// For use with `#[serde(with = "minifilepath::canonicalize")]` on [`FilePathBuf`] fields.
+///
+/// Serializes the same as [`FilePathBuf`], but deserializes any valid (maybe non-canonical) path,
+/// converting it to the canonical string representation.
+/// Same as for [`FilePathBuf`], the deserializer error only carries the message of the [`FilePathError`];
+/// see [`try_deserialize`](canonicalize::try_deserialize) for the typed error.
+///
+/// E.g.
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     #[serde(with = "minifilepath::canonicalize")]
+///     texture: minifilepath::FilePathBuf,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{ "texture": "textures//./barrel.png" }"#).unwrap();
+/// assert_eq!(config.texture.as_str(), "textures/barrel.png");
+/// ```
+pub mod canonicalize {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(path: &FilePathBuf, serializer: S) -> Result<S::Ok, S::Error> {
+        path.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FilePathBuf, D::Error> {
+        deserializer.deserialize_string(FilePathBufVisitor { canonicalize: true })
+    }
+
+    /// Like [`deserialize`], but returns the [`FilePathError`] of an invalid path as is,
+    /// instead of as the deserializer error.
+    ///
+    /// Fails with the deserializer error only if the input is not a string.
+    pub fn try_deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Result<FilePathBuf, FilePathError>, D::Error> {
+        String::deserialize(deserializer).map(FilePathBuf::try_from)
+    }
+}
+
+impl FilePathBuf {
+    /// Like the [`Deserialize`] implementation, but returns the [`FilePathError`] of an invalid or non-canonical path as is,
+    /// instead of as the deserializer error, which only carries its message.
+    ///
+    /// Fails with the deserializer error only if the input is not a string.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{ErrorPath, FilePathBuf, FilePathError};
+    ///
+    /// let mut deserializer = serde_json::Deserializer::from_str(r#""foo/../bar.txt""#);
+    /// assert_eq!(
+    ///     FilePathBuf::try_deserialize(&mut deserializer).unwrap(),
+    ///     Err(FilePathError::ParentDirectory(ErrorPath::from("foo")))
+    /// );
+    /// ```
+    pub fn try_deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Result<Self, FilePathError>, D::Error> {
+        let path = String::deserialize(deserializer)?;
+
+        let canonical = match FilePath::new(path.as_str()) {
+            Ok(file_path) => file_path.is_canonical(),
+            Err(err) => return Ok(Err(err)),
+        };
+
+        Ok(if canonical {
+            // Reuse the allocation.
+            Ok(Self(unsafe { NonEmptyString::new_unchecked(path) }))
+        } else {
+            Err(FilePathError::NonCanonicalPath(ErrorPath::from(path)))
+        })
+    }
+}
+
+struct FilePathBufVisitor {
+    canonicalize: bool,
+}
+
+impl FilePathBufVisitor {
+    fn file_path<'a, E: Error>(&self, path: &'a str) -> Result<&'a FilePath, E> {
+        let file_path = FilePath::new(path).map_err(E::custom)?;
+
+        if self.canonicalize || file_path.is_canonical() {
+            Ok(file_path)
+        } else {
+            Err(non_canonical(path))
+        }
+    }
+}
+
+impl Visitor<'_> for FilePathBufVisitor {
+    type Value = FilePathBuf;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.canonicalize {
+            "a file path string".fmt(f)
+        } else {
+            "a canonical file path string".fmt(f)
+        }
+    }
+
+    fn visit_str<E: Error>(self, path: &str) -> Result<Self::Value, E> {
+        self.file_path(path).map(FilePath::to_owned)
+    }
+
+    fn visit_string<E: Error>(self, path: String) -> Result<Self::Value, E> {
+        let file_path = self.file_path(&path)?;
+
+        if file_path.is_canonical() {
+            // Reuse the allocation.
+            Ok(FilePathBuf(unsafe { NonEmptyString::new_unchecked(path) }))
+        } else {
+            Ok(file_path.to_owned())
+        }
+    }
+}
+
+struct FilePathVisitor;
+
+impl<'de> Visitor<'de> for FilePathVisitor {
+    type Value = &'de FilePath;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        "a borrowed canonical file path string".fmt(f)
+    }
+
+    fn visit_borrowed_str<E: Error>(self, path: &'de str) -> Result<Self::Value, E> {
+        let file_path = FilePath::new(path).map_err(E::custom)?;
+
+        if file_path.is_canonical() {
+            Ok(file_path)
+        } else {
+            Err(non_canonical(path))
+        }
+    }
+}
+
+fn non_canonical<E: Error>(path: &str) -> E {
+    E::custom(FilePathError::NonCanonicalPath(ErrorPath::from(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let path = FilePath::new("foo//bar/./baz.txt").unwrap();
+        assert_eq!(serde_json::to_string(path).unwrap(), r#""foo/bar/baz.txt""#);
+        assert_eq!(
+            serde_json::to_string(&path.to_owned()).unwrap(),
+            r#""foo/bar/baz.txt""#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let json = r#""foo/bar/baz.txt""#;
+        let path: FilePathBuf = serde_json::from_str(json).unwrap();
+        assert_eq!(path.as_str(), "foo/bar/baz.txt");
+        let borrowed: &FilePath = serde_json::from_str(json).unwrap();
+        assert_eq!(borrowed.as_str(), "foo/bar/baz.txt");
+        assert_eq!(
+            serde_json::from_reader::<_, FilePathBuf>(json.as_bytes()).unwrap(),
+            path
+        );
+
+        let err = serde_json::from_str::<FilePathBuf>(r#""foo//bar/baz.txt""#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with(
+            &FilePathError::NonCanonicalPath(ErrorPath::from("foo//bar/baz.txt")).to_string()
+        ));
+        assert!(serde_json::from_str::<&FilePath>(r#""foo/./bar/baz.txt""#).is_err());
+
+        let err = serde_json::from_str::<FilePathBuf>(r#""foo/../baz.txt""#)
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string()
+                .starts_with(&FilePathError::ParentDirectory(ErrorPath::from("foo")).to_string())
+        );
+        // Escaped strings cannot be borrowed.
+        assert!(serde_json::from_str::<&FilePath>(r#""foo\/bar""#).is_err());
+    }
+
+    #[test]
+    fn try_deserialize() {
+        let try_deserialize = |json| {
+            FilePathBuf::try_deserialize(&mut serde_json::Deserializer::from_str(json)).unwrap()
+        };
+
+        assert_eq!(
+            try_deserialize(r#""foo/bar/baz.txt""#).unwrap().as_str(),
+            "foo/bar/baz.txt"
+        );
+        assert_eq!(
+            try_deserialize(r#""foo//bar/baz.txt""#).err().unwrap(),
+            FilePathError::NonCanonicalPath(ErrorPath::from("foo//bar/baz.txt"))
+        );
+        assert_eq!(
+            try_deserialize(r#""foo/../baz.txt""#).err().unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("foo"))
+        );
+        assert!(
+            FilePathBuf::try_deserialize(&mut serde_json::Deserializer::from_str("1")).is_err()
+        );
+
+        let try_canonicalize = |json| {
+            super::canonicalize::try_deserialize(&mut serde_json::Deserializer::from_str(json))
+                .unwrap()
+        };
+
+        assert_eq!(
+            try_canonicalize(r#""foo//bar/./baz.txt""#)
+                .unwrap()
+                .as_str(),
+            "foo/bar/baz.txt"
+        );
+        assert_eq!(
+            try_canonicalize(r#""/foo""#).err().unwrap(),
+            FilePathError::RootDirectory
+        );
+    }
+
+    #[test]
+    fn canonicalize() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Config {
+            #[serde(with = "super::canonicalize")]
+            path: FilePathBuf,
+        }
+
+        let config: Config = serde_json::from_str(r#"{"path":"foo//bar/./baz.txt"}"#).unwrap();
+        assert_eq!(config.path.as_str(), "foo/bar/baz.txt");
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"path":"foo/bar/baz.txt"}"#
+        );
+        assert!(serde_json::from_str::<Config>(r#"{"path":"/foo"}"#).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use {
+    crate::*,
+    core::{
+        borrow::Borrow,
+        cmp::{Ord, Ordering, PartialOrd},
+        fmt::{Display, Formatter},
+        hash::{Hash, Hasher},
+        ops::Deref,
+        str,
+    },
+    ministr::NonEmptyStr,
+};
+
+/// Maximum length in bytes of a [`SmallFilePathBuf`] which is stored inline, without a heap allocation.
+pub const SMALL_FILE_PATH_INLINE_LEN: usize = 30;
+
+/// Small-string-optimized version of a [`FilePathBuf`].
+///
+/// Stores file paths up to [`SMALL_FILE_PATH_INLINE_LEN`] bytes long inline, without a heap allocation;
+/// longer file paths are stored in a (heap-allocated) [`FilePathBuf`].
+/// Same size as a [`FilePathBuf`] plus one pointer-sized word.
+///
+/// Otherwise same as [`FilePathBuf`] - has the same canonical string representation, is hashed, compared and ordered the same way
+/// and dereferences to a [`FilePath`].
+///
+/// Useful when storing very large numbers of (mostly short) file paths.
+#[derive(Clone)]
+pub struct SmallFilePathBuf(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        buf: [u8; SMALL_FILE_PATH_INLINE_LEN],
+    },
+    Heap(FilePathBuf),
+}
+
+impl SmallFilePathBuf {
+    /// Tries to create a [`SmallFilePathBuf`] directly from a [`path`](RawPath).
+    ///
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`FilePathBuf`].
+    ///
+    /// Does not allocate if the canonical file path is at most [`SMALL_FILE_PATH_INLINE_LEN`] bytes long.
+    pub fn new<P: AsRef<RawPath>>(path: P) -> Result<Self, FilePathError> {
+        Ok(Self::from(FilePath::new(path.as_ref())?))
+    }
+
+    /// Returns the length in bytes of the [`SmallFilePathBuf`]. Always > 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        AsFilePath::len(self)
+    }
+
+    /// Returns `true` if the file path is stored inline, without a heap allocation.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Converts the [`SmallFilePathBuf`] to a [`FilePathBuilder`], without clearing it,
+    /// allowing the built path to be reused.
+    ///
+    /// Allocates if the file path is stored inline.
+    pub fn into_builder(self) -> FilePathBuilder {
+        self.into_file_path_buf().into_builder()
+    }
+
+    /// Converts the [`SmallFilePathBuf`] to a [`FilePathBuf`].
+    ///
+    /// Allocates if the file path is stored inline.
+    pub fn into_file_path_buf(self) -> FilePathBuf {
+        match self.0 {
+            Repr::Inline { .. } => self.as_file_path().to_owned(),
+            Repr::Heap(path) => path,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn into_path(self) -> std::path::PathBuf {
+        self.into_file_path_buf().into_path()
+    }
+
+    pub fn into_string(self) -> String {
+        self.into_file_path_buf().into_string()
+    }
+
+    pub fn as_file_path(&self) -> &FilePath {
+        // It is safe to directly convert a `NonEmptyStr` with a valid path to a `FilePath`.
+        unsafe { FilePath::from_str(self.as_ne_str()) }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn as_path(&self) -> &std::path::Path {
+        AsFilePath::as_path(self)
+    }
+
+    pub fn as_ne_str(&self) -> &NonEmptyStr {
+        match &self.0 {
+            // Inline buffer always contains a (non-empty) valid UTF-8 file path string.
+            Repr::Inline { len, buf } => unsafe {
+                NonEmptyStr::new_unchecked(str::from_utf8_unchecked(
+                    buf.get_unchecked(..*len as usize),
+                ))
+            },
+            Repr::Heap(path) => path.as_ne_str(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        AsFilePath::as_str(self)
+    }
+
+    /// Returns an [`iterator`](FilePathBufIter) over the [`file name`](FileName) components of the [`SmallFilePathBuf`], root to leaf.
+    ///
+    /// NOTE: file name, with extension or not, is a single component.
+    ///
+    /// NOTE: can be reversed via `rev()` to iterate leaf to root.
+    pub fn components(&self) -> FilePathBufIter<'_> {
+        AsFilePath::components(self)
+    }
+
+    /// Returns the file name portion of the [`SmallFilePathBuf`] (i.e. the last/leaf component).
+    ///
+    /// E.g.
+    /// ```
+    /// use {minifilepath::SmallFilePathBuf, ministr_macro::nestr};
+    ///
+    /// assert_eq!(SmallFilePathBuf::new("foo/bar.txt").unwrap().file_name(), nestr!("bar.txt"));
+    /// assert_eq!(SmallFilePathBuf::new("foo/bar/baz").unwrap().file_name(), nestr!("baz"));
+    /// ```
+    pub fn file_name(&self) -> &FileName {
+        AsFilePath::file_name(self)
+    }
+
+    /// Returns the file stem portion of the [`SmallFilePathBuf`] (i.e. the non-extension part of the last/leaf component).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension()`].
+    pub fn file_stem(&self) -> Option<&NonEmptyStr> {
+        AsFilePath::file_stem(self)
+    }
+
+    /// Returns the extension portion of the [`SmallFilePathBuf`] (i.e. the extension part of the last/leaf component).
+    ///
+    /// NOTE: this differs from standard library behaviour. Also see [`file_stem_and_extension()`].
+    pub fn extension(&self) -> Option<&NonEmptyStr> {
+        AsFilePath::extension(self)
+    }
+
+    /// The caller guarantees `path` is a canonical, valid file path at most `SMALL_FILE_PATH_INLINE_LEN` bytes long.
+    fn inline(path: &str) -> Self {
+        debug_assert!(!path.is_empty() && path.len() <= SMALL_FILE_PATH_INLINE_LEN);
+        let mut buf = [0; SMALL_FILE_PATH_INLINE_LEN];
+        buf[..path.len()].copy_from_slice(path.as_bytes());
+        Self(Repr::Inline {
+            len: path.len() as u8,
+            buf,
+        })
+    }
+}
+
+impl From<&FilePath> for SmallFilePathBuf {
+    fn from(path: &FilePath) -> Self {
+        let mut buf = [0; SMALL_FILE_PATH_INLINE_LEN];
+        let mut len = 0;
+
+        for component in path.components() {
+            let separator_len = if len == 0 { 0 } else { 1 };
+
+            if len + separator_len + component.len() > SMALL_FILE_PATH_INLINE_LEN {
+                return Self(Repr::Heap(path.to_owned()));
+            }
+
+            if separator_len != 0 {
+                buf[len] = SEPARATOR_BYTE;
+            }
+            len += separator_len;
+
+            buf[len..len + component.len()].copy_from_slice(component.as_bytes());
+            len += component.len();
+        }
+
+        debug_assert!(len > 0, "empty `FilePath`'s are invalid");
+
+        Self(Repr::Inline {
+            len: len as u8,
+            buf,
+        })
+    }
+}
+
+impl From<FilePathBuf> for SmallFilePathBuf {
+    /// Moves the [`FilePathBuf`] to the inline buffer, freeing its heap allocation, if it is short enough.
+    fn from(path: FilePathBuf) -> Self {
+        if path.len() <= SMALL_FILE_PATH_INLINE_LEN {
+            Self::inline(path.as_str())
+        } else {
+            Self(Repr::Heap(path))
+        }
+    }
+}
+
+impl From<SmallFilePathBuf> for FilePathBuf {
+    fn from(path: SmallFilePathBuf) -> Self {
+        path.into_file_path_buf()
+    }
+}
+
+impl AsRef<FilePath> for SmallFilePathBuf {
+    fn as_ref(&self) -> &FilePath {
+        self.as_file_path()
+    }
+}
+
+impl Deref for SmallFilePathBuf {
+    type Target = FilePath;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_file_path()
+    }
+}
+
+impl Borrow<FilePath> for SmallFilePathBuf {
+    fn borrow(&self) -> &FilePath {
+        self.as_file_path()
+    }
+}
+
+impl PartialEq<Self> for SmallFilePathBuf {
+    fn eq(&self, other: &Self) -> bool {
+        // Canonical string representations are equal iff the components are equal.
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SmallFilePathBuf {}
+
+impl Hash for SmallFilePathBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_file_path().hash(state)
+    }
+}
+
+impl PartialOrd<Self> for SmallFilePathBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SmallFilePathBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Iterator::cmp(
+            self.components().map(FileName::as_str),
+            other.components().map(FileName::as_str),
+        )
+    }
+}
+
+impl Display for SmallFilePathBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.as_ne_str().fmt(f)
+    }
+}
+
+impl core::fmt::Debug for SmallFilePathBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SmallFilePathBuf")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline() {
+        let path = SmallFilePathBuf::new("ui//icons/./ok.png").unwrap();
+        assert!(path.is_inline());
+        assert_eq!(path.as_str(), "ui/icons/ok.png");
+        assert_eq!(path.len(), "ui/icons/ok.png".len());
+        assert_eq!(path.file_name().as_str(), "ok.png");
+        assert_eq!(path.extension().unwrap().as_str(), "png");
+        assert_eq!(
+            path.components().map(FileName::as_str).collect::<Vec<_>>(),
+            ["ui", "icons", "ok.png"]
+        );
+        assert_eq!(
+            path.as_file_path(),
+            FilePath::new("ui/icons/ok.png").unwrap()
+        );
+
+        let mut builder = path.into_builder();
+        builder.push("cancel.png").unwrap();
+        assert_eq!(
+            builder.build().unwrap().as_str(),
+            "ui/icons/ok.png/cancel.png"
+        );
+
+        let inline_path = "a".repeat(SMALL_FILE_PATH_INLINE_LEN);
+        assert!(SmallFilePathBuf::new(&inline_path).unwrap().is_inline());
+
+        assert!(
+            std::mem::size_of::<SmallFilePathBuf>()
+                <= std::mem::size_of::<FilePathBuf>() + std::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn heap() {
+        let long_path = "textures/props/barrels/red_barrel.png";
+        assert!(long_path.len() > SMALL_FILE_PATH_INLINE_LEN);
+
+        let path = SmallFilePathBuf::new(long_path).unwrap();
+        assert!(!path.is_inline());
+        assert_eq!(path.as_str(), long_path);
+        assert_eq!(path.file_stem().unwrap().as_str(), "red_barrel");
+
+        // Component boundary crosses the inline length.
+        let long_path = format!("{}/b", "a".repeat(SMALL_FILE_PATH_INLINE_LEN - 1));
+        let path = SmallFilePathBuf::new(&long_path).unwrap();
+        assert!(!path.is_inline());
+        assert_eq!(path.as_str(), long_path);
+
+        assert_eq!(
+            path.into_file_path_buf(),
+            FilePathBuf::new(&long_path).unwrap()
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let path = FilePathBuf::new("foo/bar.txt").unwrap();
+        let small = SmallFilePathBuf::from(path.clone());
+        assert!(small.is_inline());
+        assert_eq!(small.as_str(), path.as_str());
+        assert_eq!(FilePathBuf::from(small.clone()), path);
+        assert_eq!(small.to_string(), "foo/bar.txt");
+
+        let mut hl = std::collections::hash_map::DefaultHasher::new();
+        let mut hr = hl.clone();
+        small.hash(&mut hl);
+        path.hash(&mut hr);
+        assert_eq!(hl.finish(), hr.finish());
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(small);
+        assert!(set.contains(path.as_file_path()));
+    }
+}
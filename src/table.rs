@@ -0,0 +1,539 @@
+use {
+    crate::*,
+    core::{
+        cmp::Ordering,
+        error::Error,
+        fmt::{Debug, Display, Formatter},
+        iter::{FromIterator, FusedIterator},
+    },
+};
+
+/// Number of consecutive [`FilePathTable`] entries which share a single restart point.
+const RESTART_INTERVAL: usize = 16;
+
+/// Size in bytes of the little-endian `u16` shared component count of an encoded [`FilePathTable`] entry.
+const SHARED_PREFIX_SIZE: usize = 2;
+
+/// Size in bytes of the little-endian `u32` entry count / blob length in the [`FilePathTable`] header.
+const HEADER_FIELD_SIZE: usize = 4;
+
+/// An error returned by [`FilePathTable::from_bytes`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FilePathTableError {
+    /// The input ended before the end of the encoded table.
+    UnexpectedEnd,
+    /// A table entry is not a valid [`FilePath`].
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+    /// The table is malformed (e.g. its entries are not sorted, contain invalid prefixes, or it has trailing data).
+    InvalidTable,
+}
+
+impl Error for FilePathTableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FilePathTableError::InvalidPath(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FilePathTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use FilePathTableError::*;
+
+        match self {
+            UnexpectedEnd => f.write_str("unexpected end of the encoded file path table"),
+            InvalidPath(err) => write!(f, "invalid file path table entry: {err}"),
+            InvalidTable => f.write_str("invalid encoded file path table"),
+        }
+    }
+}
+
+impl From<FilePathError> for FilePathTableError {
+    fn from(err: FilePathError) -> Self {
+        FilePathTableError::InvalidPath(err)
+    }
+}
+
+impl From<DecodeError> for FilePathTableError {
+    fn from(err: DecodeError) -> Self {
+        match err {
+            DecodeError::UnexpectedEnd => FilePathTableError::UnexpectedEnd,
+            DecodeError::InvalidPath(err) => FilePathTableError::InvalidPath(err),
+        }
+    }
+}
+
+/// A compact, immutable, sorted list of unique [`FilePath`]'s, stored in a single front-coded blob.
+///
+/// Each entry stores the number of leading components it shares with the previous entry,
+/// followed by the rest of its components, [`encoded`](AsFilePath::encode_components) with per-component lengths.
+/// Every 16th entry is a restart point which stores all of its components,
+/// allowing `O(log n)` [`random access`](Self::get) and [`lookups`](Self::binary_search).
+///
+/// Entries are ordered componentwise, consistent with the [`Ord`] implementation of [`FilePath`].
+///
+/// E.g.
+/// ```
+/// use minifilepath::{FilePath, FilePathTable};
+///
+/// let table = FilePathTable::new([
+///     FilePath::new("textures/props/barrel.png").unwrap(),
+///     FilePath::new("meshes/props/barrel.fbx").unwrap(),
+///     FilePath::new("textures/props/crate.png").unwrap(),
+/// ]);
+///
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table.get(0).unwrap().as_str(), "meshes/props/barrel.fbx");
+/// assert_eq!(table.binary_search(FilePath::new("textures/props/crate.png").unwrap()), Ok(2));
+///
+/// let bytes = table.to_bytes();
+/// assert_eq!(FilePathTable::from_bytes(&bytes).unwrap(), table);
+/// ```
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct FilePathTable {
+    len: usize,
+    /// Offsets of the restart point entries in the `blob`.
+    restarts: Vec<u32>,
+    blob: Vec<u8>,
+}
+
+impl FilePathTable {
+    /// Creates a [`FilePathTable`] from the `paths`, sorting them and removing duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the encoded table would exceed `u32::MAX` bytes.
+    pub fn new<P: AsRef<FilePath>, I: IntoIterator<Item = P>>(paths: I) -> Self {
+        let mut paths: Vec<P> = paths.into_iter().collect();
+        paths.sort_unstable_by(|l, r| l.as_ref().cmp(r.as_ref()));
+        paths.dedup_by(|l, r| l.as_ref() == r.as_ref());
+
+        let mut table = Self::default();
+        let mut prev: Option<&FilePath> = None;
+
+        for path in paths.iter().map(AsRef::as_ref) {
+            let shared = if table.len % RESTART_INTERVAL == 0 {
+                table.restarts.push(table.offset());
+                0
+            } else {
+                prev.map_or(0, |prev| shared_components(prev, path))
+            };
+
+            // `MAX_NUM_COMPONENTS < u16::MAX`.
+            table.blob.extend_from_slice(&(shared as u16).to_le_bytes());
+            // Sorted unique paths never share all of their components with the previous path.
+            encode_path_components(path.components().skip(shared), &mut table.blob);
+
+            table.len += 1;
+            prev = Some(path);
+        }
+
+        // Checks the total size of the blob.
+        table.offset();
+        table
+    }
+
+    /// Returns the number of entries in the [`FilePathTable`].
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the (canonical) [`FilePathBuf`] at the `index`, or `None` if the `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<FilePathBuf> {
+        let mut builder = FilePathBuilder::new();
+        if self.get_into(index, &mut builder) {
+            builder.build()
+        } else {
+            None
+        }
+    }
+
+    /// Clears the `builder` and writes the (canonical) path at the `index` into it,
+    /// allowing its buffer to be reused.
+    ///
+    /// Returns `false` (and leaves the `builder` empty) if the `index` is out of bounds.
+    pub fn get_into(&self, index: usize, builder: &mut FilePathBuilder) -> bool {
+        builder.clear();
+
+        if index >= self.len {
+            return false;
+        }
+
+        let restart = index / RESTART_INTERVAL;
+        let mut entries = self.entries(restart);
+        for _ in restart * RESTART_INTERVAL..=index {
+            entries.next_into(builder);
+        }
+
+        true
+    }
+
+    /// Returns `true` if the [`FilePathTable`] contains the `path`.
+    pub fn contains(&self, path: &FilePath) -> bool {
+        self.binary_search(path).is_ok()
+    }
+
+    /// Searches the [`FilePathTable`] for the `path`.
+    ///
+    /// Returns `Ok` with the index of the `path` if it is found,
+    /// or `Err` with the index where the `path` could be inserted while maintaining the sort order.
+    pub fn binary_search(&self, path: &FilePath) -> Result<usize, usize> {
+        // Index of the first restart point past the `path`.
+        let restart = self.restarts.partition_point(|&offset| {
+            // Restart point entries share no components with the previous entry.
+            let (_, components, _) = unsafe { decode_entry(&self.blob[offset as usize..]) };
+            cmp_components(components, path) != Ordering::Greater
+        });
+
+        let Some(restart) = restart.checked_sub(1) else {
+            return Err(0);
+        };
+
+        let start = restart * RESTART_INTERVAL;
+        let end = (start + RESTART_INTERVAL).min(self.len);
+
+        let mut builder = FilePathBuilder::new();
+        let mut entries = self.entries(restart);
+
+        for index in start..end {
+            entries.next_into(&mut builder);
+            let entry = unsafe {
+                builder
+                    .as_file_path()
+                    .unwrap_unchecked_dbg_msg("`FilePathTable` entries must be non-empty")
+            };
+            match entry.cmp(path) {
+                Ordering::Less => {}
+                Ordering::Equal => return Ok(index),
+                Ordering::Greater => return Err(index),
+            }
+        }
+
+        Err(end)
+    }
+
+    /// Returns an [`iterator`](FilePathTableIter) over all entries of the [`FilePathTable`], in order.
+    pub fn iter(&self) -> FilePathTableIter<'_> {
+        FilePathTableIter {
+            entries: self.entries(0),
+            builder: FilePathBuilder::new(),
+            len: self.len,
+        }
+    }
+
+    /// Serializes the [`FilePathTable`] to bytes:
+    /// the little-endian `u32` number of entries, the little-endian `u32` length of the blob, and the blob itself.
+    ///
+    /// Use [`from_bytes`](Self::from_bytes) to deserialize it.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(2 * HEADER_FIELD_SIZE + self.blob.len());
+        // Entry count is never greater than the blob length, which is checked on construction.
+        bytes.extend_from_slice(&(self.len as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.blob.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.blob);
+        bytes
+    }
+
+    /// Tries to deserialize a [`FilePathTable`] [`serialized`](Self::to_bytes) to the `bytes`.
+    ///
+    /// Validates all entries of the [`FilePathTable`].
+    ///
+    /// Returns an [`error`](FilePathTableError) if the `bytes` are too short, have trailing data
+    /// or do not contain a valid [`FilePathTable`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FilePathTableError> {
+        let (len, bytes) = split_u32(bytes)?;
+        let (blob_len, bytes) = split_u32(bytes)?;
+        let (mut blob, rest) = bytes
+            .split_at_checked(blob_len as usize)
+            .ok_or(FilePathTableError::UnexpectedEnd)?;
+        if !rest.is_empty() {
+            return Err(FilePathTableError::InvalidTable);
+        }
+
+        let mut table = Self {
+            len: len as usize,
+            // Do not trust the entry count before validating the entries.
+            restarts: Vec::new(),
+            blob: blob.to_vec(),
+        };
+
+        let mut prev = FilePathBuilder::new();
+        let mut builder = FilePathBuilder::new();
+
+        for index in 0..table.len {
+            let (shared, rest) = blob
+                .split_first_chunk::<SHARED_PREFIX_SIZE>()
+                .ok_or(FilePathTableError::UnexpectedEnd)?;
+            let shared = u16::from_le_bytes(*shared) as usize;
+            let (components, rest) = EncodedComponents::decode(rest)?;
+
+            let restart = index % RESTART_INTERVAL == 0;
+            if restart {
+                if shared != 0 {
+                    return Err(FilePathTableError::InvalidTable);
+                }
+                table.restarts.push((table.blob.len() - blob.len()) as u32);
+            }
+
+            builder.truncate(shared);
+            builder.try_extend(components)?;
+
+            // Must be sorted and unique, and share as many components with the previous entry as possible
+            // (or none, for restart points), same as the entries created by `new`.
+            let path = unsafe {
+                builder
+                    .as_file_path()
+                    .unwrap_unchecked_dbg_msg("`FilePathTable` entries must be non-empty")
+            };
+            if prev.as_file_path().is_some_and(|prev| {
+                prev >= path || (!restart && shared != shared_components(prev, path))
+            }) {
+                return Err(FilePathTableError::InvalidTable);
+            }
+            prev.clone_from(&builder);
+
+            blob = rest;
+        }
+
+        if blob.is_empty() {
+            Ok(table)
+        } else {
+            Err(FilePathTableError::InvalidTable)
+        }
+    }
+
+    /// Returns the offset of the end of the blob.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob length exceeds `u32::MAX` bytes.
+    fn offset(&self) -> u32 {
+        u32::try_from(self.blob.len()).expect("`FilePathTable` is too large")
+    }
+
+    /// Returns the entries starting at the `restart` point.
+    fn entries(&self, restart: usize) -> Entries<'_> {
+        Entries(
+            self.restarts
+                .get(restart)
+                .map_or(&[], |&offset| &self.blob[offset as usize..]),
+        )
+    }
+}
+
+impl<P: AsRef<FilePath>> FromIterator<P> for FilePathTable {
+    /// Creates a [`FilePathTable`] from the `paths`, sorting them and removing duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the encoded table would exceed `u32::MAX` bytes.
+    fn from_iter<I: IntoIterator<Item = P>>(paths: I) -> Self {
+        Self::new(paths)
+    }
+}
+
+impl<'a> IntoIterator for &'a FilePathTable {
+    type Item = FilePathBuf;
+    type IntoIter = FilePathTableIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Debug for FilePathTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over the entries of a [`FilePathTable`], in order.
+pub struct FilePathTableIter<'a> {
+    entries: Entries<'a>,
+    builder: FilePathBuilder,
+    len: usize,
+}
+
+impl<'a> Iterator for FilePathTableIter<'a> {
+    type Item = FilePathBuf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        self.entries.next_into(&mut self.builder);
+        self.builder.clone().build()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a> ExactSizeIterator for FilePathTableIter<'a> {}
+
+impl<'a> FusedIterator for FilePathTableIter<'a> {}
+
+/// Remaining (validated) encoded entries of a [`FilePathTable`].
+struct Entries<'a>(&'a [u8]);
+
+impl<'a> Entries<'a> {
+    /// Applies the next entry to the `builder`, which must contain the previous entry.
+    ///
+    /// The caller guarantees there is a next entry.
+    fn next_into(&mut self, builder: &mut FilePathBuilder) {
+        let (shared, components, rest) = unsafe { decode_entry(self.0) };
+        builder.truncate(shared);
+        unsafe {
+            builder
+                .try_extend(components)
+                .ok()
+                .unwrap_unchecked_dbg_msg("`FilePathTable` entries must be valid")
+        };
+        self.0 = rest;
+    }
+}
+
+/// Returns the shared component count, the rest of the components of the entry, and the rest of the `bytes`.
+///
+/// The caller guarantees the `bytes` start with a valid encoded entry.
+unsafe fn decode_entry(bytes: &[u8]) -> (usize, EncodedComponents<'_>, &[u8]) {
+    let (shared, rest) = unsafe {
+        bytes
+            .split_first_chunk::<SHARED_PREFIX_SIZE>()
+            .unwrap_unchecked_dbg_msg("invalid `FilePathTable` entry")
+    };
+    let (components, rest) = unsafe { EncodedComponents::decode_unchecked(rest) };
+    (u16::from_le_bytes(*shared) as usize, components, rest)
+}
+
+/// Returns the number of leading components shared by the paths.
+fn shared_components(l: &FilePath, r: &FilePath) -> usize {
+    Iterator::zip(l.components(), r.components())
+        .take_while(|(l, r)| l == r)
+        .count()
+}
+
+/// Compares the `components` with the `path` componentwise, same as [`FilePath`]'s.
+fn cmp_components(components: EncodedComponents<'_>, path: &FilePath) -> Ordering {
+    Iterator::cmp(
+        components.map(FileName::as_str),
+        path.components().map(FileName::as_str),
+    )
+}
+
+fn split_u32(bytes: &[u8]) -> Result<(u32, &[u8]), FilePathTableError> {
+    let (value, rest) = bytes
+        .split_first_chunk::<HEADER_FIELD_SIZE>()
+        .ok_or(FilePathTableError::UnexpectedEnd)?;
+    Ok((u32::from_le_bytes(*value), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths() -> Vec<FilePathBuf> {
+        let mut paths = Vec::new();
+        for dir in ["meshes", "textures", "textures/props", "sounds/ambient"] {
+            for i in 0..20 {
+                paths.push(FilePathBuf::new(format!("{dir}/file{i:02}.bin")).unwrap());
+            }
+        }
+        paths.push(FilePathBuf::new("textures").unwrap());
+        paths.push(FilePathBuf::new("a").unwrap());
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn table() {
+        let paths = paths();
+        let mut shuffled = paths.clone();
+        shuffled.reverse();
+        shuffled.push(paths[3].clone());
+
+        let table: FilePathTable = shuffled.iter().collect();
+        assert_eq!(table.len(), paths.len());
+        assert!(table.iter().eq(paths.iter().cloned()));
+        assert_eq!(table.iter().len(), paths.len());
+
+        let mut builder = FilePathBuilder::new();
+        for (index, path) in paths.iter().enumerate() {
+            assert_eq!(table.get(index).as_ref(), Some(path));
+            assert!(table.get_into(index, &mut builder));
+            assert_eq!(builder.as_file_path(), Some(path.as_file_path()));
+            assert_eq!(table.binary_search(path), Ok(index));
+        }
+        assert!(table.get(paths.len()).is_none());
+        assert!(!table.get_into(paths.len(), &mut builder));
+        assert!(builder.is_empty());
+
+        for path in ["0", "b", "textures/a", "textures/file05.bin/a", "z"] {
+            let path = FilePath::new(path).unwrap();
+            assert_eq!(
+                table.binary_search(path),
+                paths.binary_search_by(|p| p.as_file_path().cmp(path))
+            );
+            assert!(!table.contains(path));
+        }
+
+        let empty = FilePathTable::new(Vec::<FilePathBuf>::new());
+        assert!(empty.is_empty());
+        assert!(empty.get(0).is_none());
+        assert_eq!(empty.binary_search(FilePath::new("a").unwrap()), Err(0));
+        assert_eq!(empty.iter().count(), 0);
+    }
+
+    #[test]
+    fn bytes() {
+        let table = FilePathTable::new(paths());
+        let bytes = table.to_bytes();
+        assert_eq!(FilePathTable::from_bytes(&bytes).unwrap(), table);
+
+        let empty = FilePathTable::default();
+        assert_eq!(FilePathTable::from_bytes(&empty.to_bytes()).unwrap(), empty);
+
+        assert_eq!(
+            FilePathTable::from_bytes(&bytes[..bytes.len() - 1])
+                .err()
+                .unwrap(),
+            FilePathTableError::UnexpectedEnd
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            FilePathTable::from_bytes(&trailing).err().unwrap(),
+            FilePathTableError::InvalidTable
+        );
+
+        // Invalid entry path.
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&6u32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 1, 0, 1, b'.']);
+        assert_eq!(
+            FilePathTable::from_bytes(&bytes).err().unwrap(),
+            FilePathTableError::InvalidPath(FilePathError::CurrentDirectory(ErrorPath::new()))
+        );
+
+        // Unsorted.
+        let mut blob = Vec::new();
+        for path in ["b", "a"] {
+            blob.extend_from_slice(&[0, 0]);
+            FilePath::new(path).unwrap().encode_components(&mut blob);
+        }
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&(blob.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&blob);
+        assert_eq!(
+            FilePathTable::from_bytes(&bytes).err().unwrap(),
+            FilePathTableError::InvalidTable
+        );
+    }
+}
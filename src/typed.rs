@@ -0,0 +1,405 @@
+use {
+    crate::*,
+    core::{
+        borrow::Borrow,
+        cmp::{Ord, Ordering, PartialOrd},
+        fmt::{Debug, Display, Formatter},
+        hash::{Hash, Hasher},
+        marker::PhantomData,
+        ops::Deref,
+    },
+    ministr::NonEmptyStr,
+};
+
+/// A zero-sized marker type which describes the set of file extensions a [`TypedFilePath`] may have.
+///
+/// Usually defined via the [`extension_marker`] macro.
+pub trait ExtensionMarker {
+    /// Allowed file extensions, without the leading period (e.g. `"png"`).
+    ///
+    /// NOTE: only the last extension of the file name is checked (see [`file_stem_and_extension`]),
+    /// so multi-part extensions like `"tar.gz"` never match.
+    const EXTENSIONS: &'static [&'static str];
+
+    /// Whether the file extensions are compared case-sensitively. ASCII case-insensitive by default.
+    const CASE_SENSITIVE: bool = false;
+
+    /// Returns `true` if the `extension` is one of the allowed [`EXTENSIONS`](Self::EXTENSIONS).
+    fn matches_extension(extension: &str) -> bool {
+        Self::EXTENSIONS.iter().any(|allowed| {
+            if Self::CASE_SENSITIVE {
+                *allowed == extension
+            } else {
+                allowed.eq_ignore_ascii_case(extension)
+            }
+        })
+    }
+}
+
+/// Defines one or more [`ExtensionMarker`] types.
+///
+/// Extensions are compared ASCII case-insensitively, unless `case_sensitive` is specified.
+///
+/// E.g.
+/// ```
+/// use minifilepath::{extension_marker, TypedFilePath};
+///
+/// extension_marker! {
+///     /// Texture files.
+///     pub Texture: "png", "dds";
+///     /// Shader source files.
+///     pub Hlsl: case_sensitive "hlsl";
+/// }
+///
+/// assert!(TypedFilePath::<Texture>::new("textures/barrel.PNG").is_ok());
+/// assert!(TypedFilePath::<Texture>::new("sounds/barrel.wav").is_err());
+/// assert!(TypedFilePath::<Hlsl>::new("shaders/lit.HLSL").is_err());
+/// ```
+#[macro_export]
+macro_rules! extension_marker {
+    ($($(#[$attr:meta])* $vis:vis $name:ident: $($case_sensitive:ident)? $($extension:literal),+ $(,)?);+ $(;)?) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+            $vis struct $name;
+
+            impl $crate::ExtensionMarker for $name {
+                const EXTENSIONS: &'static [&'static str] = &[$($extension),+];
+                const CASE_SENSITIVE: bool = $crate::extension_marker!(@case_sensitive $($case_sensitive)?);
+            }
+        )+
+    };
+    (@case_sensitive case_sensitive) => {
+        true
+    };
+    (@case_sensitive) => {
+        false
+    };
+}
+
+/// Non-empty, relative, case agnostic UTF-8 file path with one of the file extensions described by the [`marker`](ExtensionMarker) `E`.
+///
+/// Same as a [`FilePath`] (which it dereferences to), but its extension is checked on construction,
+/// so e.g. a `TypedFilePath<Texture>` may be required by a texture loader to never receive a path to a sound file.
+///
+/// This is the borrowed version, [`TypedFilePathBuf`] is the owned version.
+#[repr(transparent)]
+pub struct TypedFilePath<E> {
+    marker: PhantomData<fn() -> E>,
+    path: FilePath,
+}
+
+impl<E: ExtensionMarker> TypedFilePath<E> {
+    /// Tries to create a [`TypedFilePath`] directly from a [`path`](RawPath).
+    ///
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`FilePath`]
+    /// or does not have one of the extensions described by `E`.
+    pub fn new<P: AsRef<RawPath> + ?Sized>(path: &P) -> Result<&Self, FilePathError> {
+        Self::from_file_path(FilePath::new(path)?)
+    }
+
+    /// Tries to reinterpret the (untyped) `path` as a [`TypedFilePath`].
+    ///
+    /// Returns an [`error`](FilePathError) if the `path` does not have one of the extensions described by `E`.
+    pub fn from_file_path(path: &FilePath) -> Result<&Self, FilePathError> {
+        check_extension::<E>(path)?;
+        Ok(unsafe { Self::from_file_path_unchecked(path) })
+    }
+}
+
+impl<E> TypedFilePath<E> {
+    /// Returns the [`TypedFilePath`] as an untyped [`FilePath`].
+    pub fn as_file_path(&self) -> &FilePath {
+        &self.path
+    }
+
+    /// Returns the extension portion of the path, which is always one of the extensions described by `E`.
+    pub fn extension(&self) -> &NonEmptyStr {
+        unsafe {
+            self.path
+                .extension()
+                .unwrap_unchecked_dbg_msg("`TypedFilePath`'s must have an extension")
+        }
+    }
+
+    /// The caller guarantees the `path` has one of the extensions described by `E`.
+    unsafe fn from_file_path_unchecked(path: &FilePath) -> &Self {
+        // `TypedFilePath` is a `#[repr(transparent)]` wrapper around a `FilePath`.
+        unsafe { &*(path as *const FilePath as *const TypedFilePath<E>) }
+    }
+}
+
+impl<E> Deref for TypedFilePath<E> {
+    type Target = FilePath;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+impl<E> AsRef<FilePath> for TypedFilePath<E> {
+    fn as_ref(&self) -> &FilePath {
+        &self.path
+    }
+}
+
+impl<E> ToOwned for TypedFilePath<E> {
+    type Owned = TypedFilePathBuf<E>;
+
+    fn to_owned(&self) -> Self::Owned {
+        TypedFilePathBuf {
+            marker: PhantomData,
+            path: self.path.to_owned(),
+        }
+    }
+}
+
+impl<E> PartialEq for TypedFilePath<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<E> Eq for TypedFilePath<E> {}
+
+impl<E> PartialOrd for TypedFilePath<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for TypedFilePath<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.path.cmp(&other.path)
+    }
+}
+
+impl<E> Hash for TypedFilePath<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state)
+    }
+}
+
+impl<E> Display for TypedFilePath<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.path, f)
+    }
+}
+
+impl<E> Debug for TypedFilePath<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TypedFilePath")
+            .field(&self.path.as_str())
+            .finish()
+    }
+}
+
+/// Non-empty, relative, case agnostic UTF-8 file path with one of the file extensions described by the [`marker`](ExtensionMarker) `E`.
+///
+/// Same as a [`FilePathBuf`] (i.e. uses the canonical string representation), but its extension is checked on construction.
+/// See [`TypedFilePath`].
+///
+/// This is the owned version, [`TypedFilePath`] is the borrowed version.
+pub struct TypedFilePathBuf<E> {
+    marker: PhantomData<fn() -> E>,
+    path: FilePathBuf,
+}
+
+impl<E: ExtensionMarker> TypedFilePathBuf<E> {
+    /// Tries to create a [`TypedFilePathBuf`] directly from a [`path`](RawPath).
+    ///
+    /// Returns an [`error`](FilePathError) if the [`path`](RawPath) is not a valid [`FilePathBuf`]
+    /// or does not have one of the extensions described by `E`.
+    pub fn new<P: AsRef<RawPath>>(path: P) -> Result<Self, FilePathError> {
+        Self::from_file_path_buf(FilePathBuf::new(path)?)
+    }
+
+    /// Tries to reinterpret the (untyped) `path` as a [`TypedFilePathBuf`].
+    ///
+    /// Returns an [`error`](FilePathError) if the `path` does not have one of the extensions described by `E`.
+    pub fn from_file_path_buf(path: FilePathBuf) -> Result<Self, FilePathError> {
+        check_extension::<E>(&path)?;
+        Ok(Self {
+            marker: PhantomData,
+            path,
+        })
+    }
+}
+
+impl<E> TypedFilePathBuf<E> {
+    pub fn as_typed_file_path(&self) -> &TypedFilePath<E> {
+        unsafe { TypedFilePath::from_file_path_unchecked(self.path.as_file_path()) }
+    }
+
+    /// Converts the [`TypedFilePathBuf`] to an untyped [`FilePathBuf`].
+    pub fn into_file_path_buf(self) -> FilePathBuf {
+        self.path
+    }
+
+    pub fn into_string(self) -> String {
+        self.path.into_string()
+    }
+}
+
+impl<E> Deref for TypedFilePathBuf<E> {
+    type Target = TypedFilePath<E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_typed_file_path()
+    }
+}
+
+impl<E> AsRef<TypedFilePath<E>> for TypedFilePathBuf<E> {
+    fn as_ref(&self) -> &TypedFilePath<E> {
+        self.as_typed_file_path()
+    }
+}
+
+impl<E> AsRef<FilePath> for TypedFilePathBuf<E> {
+    fn as_ref(&self) -> &FilePath {
+        self.path.as_file_path()
+    }
+}
+
+impl<E> Borrow<TypedFilePath<E>> for TypedFilePathBuf<E> {
+    fn borrow(&self) -> &TypedFilePath<E> {
+        self.as_typed_file_path()
+    }
+}
+
+impl<E> From<&TypedFilePath<E>> for TypedFilePathBuf<E> {
+    fn from(path: &TypedFilePath<E>) -> Self {
+        path.to_owned()
+    }
+}
+
+impl<E> From<TypedFilePathBuf<E>> for FilePathBuf {
+    fn from(path: TypedFilePathBuf<E>) -> Self {
+        path.path
+    }
+}
+
+impl<E> Clone for TypedFilePathBuf<E> {
+    fn clone(&self) -> Self {
+        Self {
+            marker: PhantomData,
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<E> PartialEq for TypedFilePathBuf<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<E> Eq for TypedFilePathBuf<E> {}
+
+impl<E> PartialOrd for TypedFilePathBuf<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for TypedFilePathBuf<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.path.cmp(&other.path)
+    }
+}
+
+impl<E> Hash for TypedFilePathBuf<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state)
+    }
+}
+
+impl<E> Display for TypedFilePathBuf<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.path, f)
+    }
+}
+
+impl<E> Debug for TypedFilePathBuf<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TypedFilePathBuf")
+            .field(&self.path.as_str())
+            .finish()
+    }
+}
+
+fn check_extension<E: ExtensionMarker>(path: &FilePath) -> Result<(), FilePathError> {
+    if file_stem_and_extension(path.file_name())
+        .is_some_and(|split| E::matches_extension(split.extension.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(FilePathError::InvalidExtension(ErrorPath::from(
+            path.as_str(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extension_marker! {
+        Texture: "png", "dds";
+        Hlsl: case_sensitive "hlsl";
+    }
+
+    #[test]
+    fn typed() {
+        let texture = TypedFilePath::<Texture>::new("textures//barrel.png").unwrap();
+        assert_eq!(texture.extension(), "png");
+        assert_eq!(texture.file_name(), "barrel.png");
+
+        let texture = texture.to_owned();
+        assert_eq!(texture.as_str(), "textures/barrel.png");
+        assert_eq!(
+            *texture,
+            *TypedFilePath::<Texture>::new("textures/barrel.png").unwrap()
+        );
+        assert_eq!(
+            FilePathBuf::from(texture),
+            FilePathBuf::new("textures/barrel.png").unwrap()
+        );
+
+        assert_eq!(
+            TypedFilePathBuf::<Texture>::new("textures/BARREL.DDS")
+                .unwrap()
+                .extension(),
+            "DDS"
+        );
+        assert!(TypedFilePath::<Hlsl>::new("shaders/lit.hlsl").is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            TypedFilePath::<Texture>::new("sounds/barrel.wav")
+                .err()
+                .unwrap(),
+            FilePathError::InvalidExtension(ErrorPath::from("sounds/barrel.wav"))
+        );
+        assert_eq!(
+            TypedFilePathBuf::<Texture>::new("textures/png")
+                .err()
+                .unwrap(),
+            FilePathError::InvalidExtension(ErrorPath::from("textures/png"))
+        );
+        assert_eq!(
+            TypedFilePath::<Hlsl>::new("shaders/lit.HLSL")
+                .err()
+                .unwrap(),
+            FilePathError::InvalidExtension(ErrorPath::from("shaders/lit.HLSL"))
+        );
+        assert_eq!(
+            TypedFilePath::<Texture>::new("textures/../barrel.png")
+                .err()
+                .unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("textures"))
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use {
+    crate::*,
+    core::{
+        error::Error,
+        fmt::{Display, Formatter, Write},
+    },
+};
+
+/// An error returned when converting URL paths to [`FilePathBuf`]'s, or building `file://` URLs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UrlPathError {
+    /// URL path contains an invalid percent-encoded byte (not followed by two hex digits).
+    /// Contains the byte offset of the `%` in the URL path.
+    InvalidPercentEncoding(usize),
+    /// URL path component contains a percent-encoded path separator (`%2F`, or the Windows `%5C`).
+    /// Contains the byte offset of the `%` in the URL path.
+    EncodedSeparator(usize),
+    /// The decoded path is not a valid [`FilePath`].
+    /// Contains the validation error.
+    InvalidPath(FilePathError),
+    /// The root directory of a `file://` URL is not an absolute UTF-8 path.
+    InvalidRoot,
+}
+
+impl Error for UrlPathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UrlPathError::InvalidPath(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for UrlPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use UrlPathError::*;
+
+        match self {
+            InvalidPercentEncoding(offset) => {
+                write!(f, "invalid percent-encoding in URL path at offset {offset}")
+            }
+            EncodedSeparator(offset) => write!(
+                f,
+                "URL path contains an encoded path separator at offset {offset}"
+            ),
+            InvalidPath(err) => write!(f, "invalid URL path: {err}"),
+            InvalidRoot => "root directory is not an absolute UTF-8 path".fmt(f),
+        }
+    }
+}
+
+impl From<FilePathError> for UrlPathError {
+    fn from(err: FilePathError) -> Self {
+        UrlPathError::InvalidPath(err)
+    }
+}
+
+impl FilePathBuf {
+    /// Tries to decode a (relative) URL path, as returned by [`AsFilePath::to_url_path`], into a [`FilePathBuf`].
+    ///
+    /// Percent-encoded bytes are decoded as UTF-8. `/` separates the path components;
+    /// percent-encoded separators (`%2F`, or the Windows `%5C`) within components are rejected.
+    ///
+    /// E.g.
+    /// ```
+    /// use minifilepath::{FilePathBuf, UrlPathError};
+    ///
+    /// let path = FilePathBuf::from_url_path("textures/barrel%20%231/caf%C3%A9.png").unwrap();
+    /// assert_eq!(path.as_str(), "textures/barrel #1/café.png");
+    /// assert_eq!(FilePathBuf::from_url_path("foo%2Fbar").err().unwrap(), UrlPathError::EncodedSeparator(3));
+    /// ```
+    pub fn from_url_path(url: &str) -> Result<Self, UrlPathError> {
+        let bytes = url.as_bytes();
+        let mut path = Vec::with_capacity(bytes.len());
+        let mut idx = 0;
+
+        while let Some(&b) = bytes.get(idx) {
+            if b == b'%' {
+                let decoded = bytes
+                    .get(idx + 1..idx + 3)
+                    .and_then(|hex| str::from_utf8(hex).ok())
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or(UrlPathError::InvalidPercentEncoding(idx))?;
+                if decoded == SEPARATOR_BYTE || decoded == b'\\' {
+                    return Err(UrlPathError::EncodedSeparator(idx));
+                }
+                path.push(decoded);
+                idx += 3;
+            } else {
+                path.push(b);
+                idx += 1;
+            }
+        }
+
+        let path = String::from_utf8(path).map_err(|err| {
+            FilePathError::InvalidUTF8(ErrorPath::from(
+                String::from_utf8_lossy(err.as_bytes()).as_ref(),
+            ))
+        })?;
+
+        Ok(Self::try_from(path)?)
+    }
+}
+
+/// Returns the `file://` URL of the `path` in the absolute `root` directory.
+///
+/// The `root` must be an absolute UTF-8 path (on Windows, on a drive, e.g. `C:\assets`).
+///
+/// E.g.
+/// ```
+/// # #[cfg(unix)] {
+/// use minifilepath::{file_url, FilePath};
+///
+/// let url = file_url("/home/user/my assets", FilePath::new("textures/café.png").unwrap()).unwrap();
+/// assert_eq!(url, "file:///home/user/my%20assets/textures/caf%C3%A9.png");
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn file_url<P: AsRef<std::path::Path>>(
+    root: P,
+    path: &FilePath,
+) -> Result<String, UrlPathError> {
+    use std::path::{Component, Prefix};
+
+    let root = root.as_ref();
+    if !root.is_absolute() {
+        return Err(UrlPathError::InvalidRoot);
+    }
+
+    let mut url = String::from("file://");
+
+    for component in root.components() {
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => {
+                    write!(url, "/{}:", drive as char).unwrap();
+                }
+                _ => return Err(UrlPathError::InvalidRoot),
+            },
+            Component::RootDir => {}
+            Component::Normal(component) => {
+                url.push(SEPARATOR_CHAR);
+                percent_encode(
+                    component.to_str().ok_or(UrlPathError::InvalidRoot)?,
+                    &mut url,
+                );
+            }
+            Component::CurDir | Component::ParentDir => return Err(UrlPathError::InvalidRoot),
+        }
+    }
+
+    url.push(SEPARATOR_CHAR);
+    url.push_str(&path.to_url_path());
+
+    Ok(url)
+}
+
+/// See [`AsFilePath::to_url_path`].
+pub(crate) fn url_path<'a, I: Iterator<Item = &'a FileName>>(components: I, len: usize) -> String {
+    let mut url = String::with_capacity(len);
+
+    for (idx, component) in components.enumerate() {
+        if idx > 0 {
+            url.push(SEPARATOR_CHAR);
+        }
+        percent_encode(component.as_str(), &mut url);
+    }
+
+    url
+}
+
+/// Appends the `component`, percent-encoded per RFC 3986 `pchar`, to the `url`.
+fn percent_encode(component: &str, url: &mut String) {
+    for b in component.bytes() {
+        if is_pchar(b) {
+            url.push(b as char);
+        } else {
+            write!(url, "%{b:02X}").unwrap();
+        }
+    }
+}
+
+/// `pchar = unreserved / pct-encoded / sub-delims / ":" / "@"`
+fn is_pchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric()
+        || matches!(
+            b,
+            // unreserved
+            b'-' | b'.' | b'_' | b'~'
+            // sub-delims
+            | b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'='
+            | b':' | b'@'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_path() {
+        for (path, url) in [
+            ("foo/bar.txt", "foo/bar.txt"),
+            ("foo bar/100%.txt", "foo%20bar/100%25.txt"),
+            ("a+b/[c]{d}/e@f;g=h/~i'j", "a+b/%5Bc%5D%7Bd%7D/e@f;g=h/~i'j"),
+            ("日本/é#^.png", "%E6%97%A5%E6%9C%AC/%C3%A9%23%5E.png"),
+        ] {
+            let path = FilePath::new(path).unwrap();
+            assert_eq!(path.to_url_path(), url);
+            assert_eq!(FilePathBuf::from_url_path(url).unwrap(), path);
+        }
+
+        assert_eq!(
+            FilePath::new("foo/./bar.txt").unwrap().to_url_path(),
+            "foo/bar.txt"
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/%62ar%2etxt")
+                .unwrap()
+                .as_str(),
+            "foo/bar.txt"
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo//./bar.txt")
+                .unwrap()
+                .as_str(),
+            "foo/bar.txt"
+        );
+    }
+
+    #[test]
+    fn invalid_url_path() {
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/bar%2fbaz").err().unwrap(),
+            UrlPathError::EncodedSeparator(7)
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/bar%5Cbaz").err().unwrap(),
+            UrlPathError::EncodedSeparator(7)
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/bar%2").err().unwrap(),
+            UrlPathError::InvalidPercentEncoding(7)
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/bar%g0").err().unwrap(),
+            UrlPathError::InvalidPercentEncoding(7)
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("foo/%2E%2E/bar").err().unwrap(),
+            UrlPathError::InvalidPath(FilePathError::ParentDirectory(ErrorPath::from("foo")))
+        );
+        assert_eq!(
+            FilePathBuf::from_url_path("/foo").err().unwrap(),
+            UrlPathError::InvalidPath(FilePathError::RootDirectory)
+        );
+        assert!(matches!(
+            FilePathBuf::from_url_path("foo/%C3").err().unwrap(),
+            UrlPathError::InvalidPath(FilePathError::InvalidUTF8(_))
+        ));
+    }
+
+    #[cfg(all(feature = "std", unix))]
+    #[test]
+    fn file_url() {
+        let path = FilePath::new("textures/barrel #1.png").unwrap();
+
+        assert_eq!(
+            super::file_url("/srv/assets", path).unwrap(),
+            "file:///srv/assets/textures/barrel%20%231.png"
+        );
+        assert_eq!(
+            super::file_url("/", path).unwrap(),
+            "file:///textures/barrel%20%231.png"
+        );
+        assert_eq!(
+            super::file_url("srv/assets", path).err().unwrap(),
+            UrlPathError::InvalidRoot
+        );
+        assert_eq!(
+            super::file_url("/srv/../assets", path).err().unwrap(),
+            UrlPathError::InvalidRoot
+        );
+    }
+}
@@ -1,11 +1,17 @@
 use {
     crate::*,
+    core::fmt::{Display, Formatter},
     ministr::NonEmptyStr,
-    std::path::{Component, Path, PathBuf},
 };
 
-pub(crate) fn validate_path_component<F: FnOnce() -> PathBuf>(
-    component: FilePathComponent,
+#[cfg(feature = "std")]
+use std::path::Component;
+
+#[cfg(not(feature = "std"))]
+use crate::iter::raw_components;
+
+pub(crate) fn validate_path_component<F: FnOnce() -> ErrorPath>(
+    component: &NonEmptyStr,
     f: F,
 ) -> Result<(), FilePathError> {
     let len = component.len();
@@ -43,8 +49,28 @@ pub(crate) fn validate_path_component<F: FnOnce() -> PathBuf>(
     Ok(())
 }
 
+/// Like `validate_path_component()`, but also disallows current (`"."`) / parent (`".."`) directory components.
+///
+/// `f` returns the path to the parent directory of the `component`.
+pub(crate) fn validate_normal_path_component<F: FnOnce() -> ErrorPath>(
+    component: &NonEmptyStr,
+    f: F,
+) -> Result<(), FilePathError> {
+    if component == "." {
+        Err(FilePathError::CurrentDirectory(f()))
+    } else if component == ".." {
+        Err(FilePathError::ParentDirectory(f()))
+    } else {
+        validate_path_component(component, || {
+            let mut path = f();
+            push_error_path(&mut path, component);
+            path
+        })
+    }
+}
+
 /// Like `str::split_once(...)`, but splits (case-insensitively) on one of the Windows reserved file names.
-fn split_at_reserved_name(component: FilePathComponent<'_>) -> Option<(&str, &str)> {
+fn split_at_reserved_name(component: &NonEmptyStr) -> Option<(&str, &str)> {
     // None of the reserved name match sequences overlap, except `CON` / `COM?`, which diverge on their 3rd matched character,
     // which allows us to implement this efficiently by only ever tracking at most a single match sequence.
 
@@ -376,7 +402,62 @@ fn split_at_reserved_name(component: FilePathComponent<'_>) -> Option<(&str, &st
         })
 }
 
-pub(crate) fn validate_path<P: AsRef<Path>>(path: P) -> Result<(), FilePathError> {
+/// Appends the `component` to the (maybe empty) `path` contained in a [`FilePathError`].
+#[cfg(feature = "std")]
+pub(crate) fn push_error_path(path: &mut ErrorPath, component: &str) {
+    path.push(component);
+}
+
+/// Appends the `component` to the (maybe empty) `path` contained in a [`FilePathError`].
+#[cfg(not(feature = "std"))]
+pub(crate) fn push_error_path(path: &mut ErrorPath, component: &str) {
+    if !path.is_empty() {
+        path.push(SEPARATOR_CHAR);
+    }
+    path.push_str(component);
+}
+
+/// Returns the `path` as a UTF-8 string, if it is one.
+#[cfg(feature = "std")]
+pub(crate) fn raw_path_to_str(path: &RawPath) -> Option<&str> {
+    path.to_str()
+}
+
+/// Returns the `path` as a UTF-8 string, if it is one.
+#[cfg(not(feature = "std"))]
+pub(crate) fn raw_path_to_str(path: &RawPath) -> Option<&str> {
+    Some(path)
+}
+
+/// Returns the length in bytes of the `path`.
+#[cfg(feature = "std")]
+pub(crate) fn raw_path_len(path: &RawPath) -> usize {
+    path.as_os_str().len()
+}
+
+/// Returns the length in bytes of the `path`.
+#[cfg(not(feature = "std"))]
+pub(crate) fn raw_path_len(path: &RawPath) -> usize {
+    path.len()
+}
+
+/// Formats the [`FilePath`] in the canonical string representation.
+pub(crate) struct Canonical<'a>(pub(crate) &'a FilePath);
+
+impl Display for Canonical<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (idx, component) in self.0.components().enumerate() {
+            if idx > 0 {
+                SEPARATOR_CHAR.fmt(f)?;
+            }
+            component.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn validate_path<P: AsRef<RawPath>>(path: P) -> Result<(), FilePathError> {
     use FilePathError::*;
 
     let path = path.as_ref();
@@ -386,7 +467,7 @@ pub(crate) fn validate_path<P: AsRef<Path>>(path: P) -> Result<(), FilePathError
     let get_path = |idx: usize, include_self: bool| {
         path.components()
             .take(if include_self { idx + 1 } else { idx })
-            .collect::<PathBuf>()
+            .collect::<ErrorPath>()
     };
 
     for (idx, comp) in path.components().enumerate() {
@@ -424,6 +505,61 @@ pub(crate) fn validate_path<P: AsRef<Path>>(path: P) -> Result<(), FilePathError
     }
 }
 
+/// String-based version of the path validation, consistent with [`std::path::Path`]'s parsing on Unix platforms:
+/// repeated separators and mid-path current directory components (`.`) are skipped.
+#[cfg(not(feature = "std"))]
+pub(crate) fn validate_path<P: AsRef<RawPath>>(path: P) -> Result<(), FilePathError> {
+    use FilePathError::*;
+
+    let path = path.as_ref();
+
+    if path.starts_with(SEPARATOR_CHAR) {
+        return Err(RootDirectory);
+    }
+
+    // Only the leading current directory component is not skipped, same as `std::path::Path`.
+    if path.split(SEPARATOR_CHAR).next() == Some(".") {
+        return Err(CurrentDirectory(ErrorPath::new()));
+    }
+
+    let mut path_len: usize = 0;
+
+    let get_path = |idx: usize, include_self: bool| {
+        raw_components(path)
+            .take(if include_self { idx + 1 } else { idx })
+            .fold(ErrorPath::new(), |mut path, component| {
+                push_error_path(&mut path, component);
+                path
+            })
+    };
+
+    for (idx, comp) in raw_components(path).enumerate() {
+        if comp == ".." {
+            return Err(ParentDirectory(get_path(idx, false)));
+        }
+
+        // Must succeed - `raw_components()` skips empty path components.
+        let comp = unsafe { NonEmptyStr::new_unchecked(comp) };
+
+        validate_path_component(comp, || get_path(idx, true))?;
+
+        // Count the separator.
+        if path_len != 0 {
+            path_len += 1;
+        }
+
+        path_len += comp.len();
+    }
+
+    if path_len == 0 {
+        Err(EmptyPath)
+    } else if path_len > MAX_PATH_LEN {
+        Err(PathTooLong(path_len))
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, ministr_macro::nestr};
@@ -467,7 +603,7 @@ mod tests {
     }
 
     fn validate_path_component_(component: &NonEmptyStr) -> Result<(), FilePathError> {
-        validate_path_component(component, PathBuf::new)
+        validate_path_component(component, ErrorPath::new)
     }
 
     #[allow(non_snake_case)]
@@ -475,61 +611,61 @@ mod tests {
     fn InvalidCharacter() {
         assert_eq!(
             validate_path_component_(nestr!("/foo")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '/'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '/'))
         );
         assert_eq!(
             validate_path_component_(nestr!("f/oo")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '/'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '/'))
         );
         assert_eq!(
             validate_path_component_(nestr!("foo\\")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '\\'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '\\'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("C:foo")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), ':'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), ':'))
         );
         assert_eq!(
             validate_path_component_(nestr!(":foo")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), ':'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), ':'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("\"foo\"")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '\"'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '\"'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("foo?")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '?'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '?'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("f*oo")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '*'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '*'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("foo<")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '<'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '<'))
         );
         assert_eq!(
             validate_path_component_(nestr!("foo>")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '>'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '>'))
         );
 
         assert_eq!(
             validate_path_component_(nestr!("foo|")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '|'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '|'))
         );
         assert_eq!(
             validate_path_component_(nestr!("foo\n")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '\n'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '\n'))
         );
         assert_eq!(
             validate_path_component_(nestr!("bar\x1b")).err().unwrap(),
-            FilePathError::InvalidCharacter((PathBuf::new(), '\x1b'))
+            FilePathError::InvalidCharacter((ErrorPath::new(), '\x1b'))
         );
 
         // But this works.
@@ -542,15 +678,15 @@ mod tests {
     fn ComponentEndsWithAPeriod() {
         assert_eq!(
             validate_path_component_(nestr!("...")).err().unwrap(),
-            FilePathError::ComponentEndsWithAPeriod(PathBuf::new())
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("foo.")).err().unwrap(),
-            FilePathError::ComponentEndsWithAPeriod(PathBuf::new())
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("NUL.")).err().unwrap(),
-            FilePathError::ComponentEndsWithAPeriod(PathBuf::new())
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::new())
         );
     }
 
@@ -559,15 +695,15 @@ mod tests {
     fn ComponentEndsWithASpace() {
         assert_eq!(
             validate_path_component_(nestr!("foo ")).err().unwrap(),
-            FilePathError::ComponentEndsWithASpace(PathBuf::new())
+            FilePathError::ComponentEndsWithASpace(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("foo . ")).err().unwrap(),
-            FilePathError::ComponentEndsWithASpace(PathBuf::new())
+            FilePathError::ComponentEndsWithASpace(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("LPT7 ")).err().unwrap(),
-            FilePathError::ComponentEndsWithASpace(PathBuf::new())
+            FilePathError::ComponentEndsWithASpace(ErrorPath::new())
         );
 
         // But this works.
@@ -579,51 +715,51 @@ mod tests {
     fn ReservedName() {
         assert_eq!(
             validate_path_component_(nestr!("COM0")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("COM9")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("CON")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!(" AUX")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("NUL.txt")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("LPT0 .txt.bmp"))
                 .err()
                 .unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("LPT9")).err().unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("CONIN$.txt"))
                 .err()
                 .unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("CONIN$.txt.bmp"))
                 .err()
                 .unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
         assert_eq!(
             validate_path_component_(nestr!("CONOUT$ . bmp"))
                 .err()
                 .unwrap(),
-            FilePathError::ReservedName(PathBuf::new())
+            FilePathError::ReservedName(ErrorPath::new())
         );
 
         // But this works.
@@ -642,4 +778,65 @@ mod tests {
         validate_path_component_(nestr!(".NUL")).unwrap();
         validate_path_component_(nestr!("foo.PRN")).unwrap();
     }
+
+    #[test]
+    fn validate_path_() {
+        assert_eq!(
+            validate_path("./a").err().unwrap(),
+            FilePathError::CurrentDirectory(ErrorPath::new())
+        );
+        validate_path("a/./b").unwrap();
+        validate_path("a//b/").unwrap();
+        assert_eq!(
+            validate_path("a/../b").err().unwrap(),
+            FilePathError::ParentDirectory(ErrorPath::from("a"))
+        );
+        assert_eq!(
+            validate_path("/a").err().unwrap(),
+            FilePathError::RootDirectory
+        );
+        assert_eq!(validate_path("").err().unwrap(), FilePathError::EmptyPath);
+        assert_eq!(
+            validate_path("a/b/c.").err().unwrap(),
+            FilePathError::ComponentEndsWithAPeriod(ErrorPath::from("a/b/c."))
+        );
+
+        // `MAX_PATH_LEN` bytes, with separators.
+        let component = "a".repeat(MAX_COMPONENT_LEN);
+        let mut path = vec![component.as_str(); MAX_PATH_LEN / (MAX_COMPONENT_LEN + 1)].join("/");
+        path.push('/');
+        path.push_str(&"b".repeat(MAX_PATH_LEN - path.len()));
+        assert_eq!(path.len(), MAX_PATH_LEN);
+        validate_path(path.as_str()).unwrap();
+        // Repeated separators and current directory components do not count.
+        validate_path(path.replace('/', "//./").as_str()).unwrap();
+
+        path.push_str("/b");
+        assert_eq!(
+            validate_path(path.as_str()).err().unwrap(),
+            FilePathError::PathTooLong(MAX_PATH_LEN + 2)
+        );
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn no_std() {
+        use crate::iter::raw_components;
+
+        assert_eq!(raw_components("a//b/").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(raw_components("a/./b/.").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(raw_components("./a").collect::<Vec<_>>(), ["a"]);
+        assert_eq!(
+            raw_components("a/../b").collect::<Vec<_>>(),
+            ["a", "..", "b"]
+        );
+
+        let mut path = ErrorPath::new();
+        push_error_path(&mut path, "a");
+        push_error_path(&mut path, "b");
+        assert_eq!(path, "a/b");
+
+        assert_eq!(raw_path_len("a//b/"), 5);
+        assert_eq!(raw_path_to_str("a//b/"), Some("a//b/"));
+    }
 }